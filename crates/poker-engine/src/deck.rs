@@ -0,0 +1,59 @@
+use crate::card::*;
+
+pub const DECK_SIZE: usize = 52;
+
+/// A 52-card deck in dealing order
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Deck {
+    cards: [Card; DECK_SIZE],
+}
+
+impl Deck {
+    /// Unshuffled deck, suit by suit from the Two of Clubs to the Ace of Spades
+    pub fn new() -> Self {
+        let mut cards = [Card::new(Suit::Clubs, Rank::Two); DECK_SIZE];
+        for (i, card) in cards.iter_mut().enumerate() {
+            *card = Card::from_u8(i as u8);
+        }
+        Self { cards }
+    }
+
+    /// Deck shuffled deterministically from `seed`
+    pub fn shuffled(seed: &[u8]) -> Self {
+        let mut deck = Self::new();
+        deck.shuffle(seed);
+        deck
+    }
+
+    /// Fisher-Yates shuffle driven by an LCG seeded from `seed`.
+    ///
+    /// Not secure: the LCG only keeps 64 bits of `seed`, and anyone who knows
+    /// the seed can recompute the whole deck. Use it for simulation and for
+    /// seeds nobody else can learn until the hand is over.
+    pub fn shuffle(&mut self, seed: &[u8]) {
+        let mut rng_seed = 0u64;
+        for (i, &byte) in seed.iter().enumerate() {
+            rng_seed ^= (byte as u64) << (i % 8 * 8);
+        }
+
+        for i in (1..self.cards.len()).rev() {
+            rng_seed = rng_seed.wrapping_mul(1103515245).wrapping_add(12345);
+            let j = (rng_seed as usize) % (i + 1);
+            self.cards.swap(i, j);
+        }
+    }
+
+    pub fn card(&self, index: usize) -> Option<Card> {
+        self.cards.get(index).copied()
+    }
+
+    pub fn cards(&self) -> &[Card; DECK_SIZE] {
+        &self.cards
+    }
+}
+
+impl Default for Deck {
+    fn default() -> Self {
+        Self::new()
+    }
+}
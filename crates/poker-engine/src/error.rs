@@ -0,0 +1,44 @@
+use core::fmt;
+
+/// Rule violations reported by the engine
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EngineError {
+    TableFull,
+    InvalidSeat,
+    SeatOccupied,
+    AlreadySeated,
+    NotEnoughPlayers,
+    GameInProgress,
+    NotPlayersTurn,
+    AlreadyActed,
+    InvalidAction,
+    InsufficientFunds,
+    RaiseTooSmall,
+    PlayerNotFound,
+    CannotLeaveDuringGame,
+    MathOverflow,
+    InvalidGameState,
+}
+
+impl fmt::Display for EngineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            EngineError::TableFull => "Table is full",
+            EngineError::InvalidSeat => "Invalid seat number",
+            EngineError::SeatOccupied => "Seat already occupied",
+            EngineError::AlreadySeated => "Player already seated at table",
+            EngineError::NotEnoughPlayers => "Not enough players to start game",
+            EngineError::GameInProgress => "Game already in progress",
+            EngineError::NotPlayersTurn => "Not player's turn",
+            EngineError::AlreadyActed => "Player has already acted this round",
+            EngineError::InvalidAction => "Invalid action",
+            EngineError::InsufficientFunds => "Insufficient funds",
+            EngineError::RaiseTooSmall => "Raise amount too small",
+            EngineError::PlayerNotFound => "Player not found at table",
+            EngineError::CannotLeaveDuringGame => "Cannot leave during active game",
+            EngineError::MathOverflow => "Math overflow",
+            EngineError::InvalidGameState => "Invalid game state",
+        };
+        f.write_str(msg)
+    }
+}
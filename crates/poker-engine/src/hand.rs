@@ -0,0 +1,136 @@
+use crate::card::*;
+
+/// Poker hand category, weakest first
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HandCategory {
+    HighCard = 0,
+    OnePair = 1,
+    TwoPair = 2,
+    ThreeOfAKind = 3,
+    Straight = 4,
+    Flush = 5,
+    FullHouse = 6,
+    FourOfAKind = 7,
+    StraightFlush = 8,
+}
+
+/// Strength of the best five-card hand. Values compare the way hands do at showdown.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HandValue {
+    pub category: HandCategory,
+    /// Tie-break ranks, most significant first. Unused trailing slots are `Rank::Two`.
+    pub ranks: [Rank; 5],
+}
+
+impl HandValue {
+    fn new(category: HandCategory, significant: &[Rank]) -> Self {
+        let mut ranks = [Rank::Two; 5];
+        ranks[..significant.len()].copy_from_slice(significant);
+        Self { category, ranks }
+    }
+}
+
+/// Evaluate the best five-card hand that can be made from `cards` (up to 7)
+pub fn evaluate(cards: &[Card]) -> HandValue {
+    let mut counts = [0u8; 13];
+    let mut suit_masks = [0u16; 4];
+    let mut rank_mask = 0u16;
+    for card in cards {
+        counts[card.rank as usize] += 1;
+        suit_masks[card.suit as usize] |= 1 << card.rank as u16;
+        rank_mask |= 1 << card.rank as u16;
+    }
+
+    let straight_flush = suit_masks.iter().filter_map(|&mask| straight_high(mask)).max();
+    if let Some(high) = straight_flush {
+        return HandValue::new(HandCategory::StraightFlush, &[high]);
+    }
+
+    let quads = highest_with_count(&counts, 4, None);
+    if let Some(quad) = quads {
+        let kicker = top_ranks(rank_mask & !bit(quad), 1);
+        return HandValue::new(HandCategory::FourOfAKind, &[quad, kicker[0]]);
+    }
+
+    let trips = highest_with_count(&counts, 3, None);
+    if let Some(trip) = trips {
+        // The pair of a full house may come from a second set of trips
+        let second_trips = highest_with_count(&counts, 3, Some(trip));
+        let pair = highest_with_count(&counts, 2, None);
+        let filler = match (second_trips, pair) {
+            (Some(a), Some(b)) => Some(a.max(b)),
+            (a, b) => a.or(b),
+        };
+        if let Some(filler) = filler {
+            return HandValue::new(HandCategory::FullHouse, &[trip, filler]);
+        }
+    }
+
+    let flush = suit_masks
+        .iter()
+        .filter(|mask| mask.count_ones() >= 5)
+        .map(|&mask| top_ranks(mask, 5))
+        .max();
+    if let Some(flush) = flush {
+        return HandValue::new(HandCategory::Flush, &flush);
+    }
+
+    if let Some(high) = straight_high(rank_mask) {
+        return HandValue::new(HandCategory::Straight, &[high]);
+    }
+
+    if let Some(trip) = trips {
+        let kickers = top_ranks(rank_mask & !bit(trip), 2);
+        return HandValue::new(HandCategory::ThreeOfAKind, &[trip, kickers[0], kickers[1]]);
+    }
+
+    if let Some(high_pair) = highest_with_count(&counts, 2, None) {
+        if let Some(low_pair) = highest_with_count(&counts, 2, Some(high_pair)) {
+            let kicker = top_ranks(rank_mask & !bit(high_pair) & !bit(low_pair), 1);
+            return HandValue::new(HandCategory::TwoPair, &[high_pair, low_pair, kicker[0]]);
+        }
+        let kickers = top_ranks(rank_mask & !bit(high_pair), 3);
+        return HandValue::new(
+            HandCategory::OnePair,
+            &[high_pair, kickers[0], kickers[1], kickers[2]],
+        );
+    }
+
+    HandValue::new(HandCategory::HighCard, &top_ranks(rank_mask, 5))
+}
+
+fn bit(rank: Rank) -> u16 {
+    1 << rank as u16
+}
+
+/// Highest rank appearing exactly `count` times, ignoring `below` and anything above it
+fn highest_with_count(counts: &[u8; 13], count: u8, below: Option<Rank>) -> Option<Rank> {
+    let start = below.map_or(13, |rank| rank as usize);
+    (0..start).rev().find(|&r| counts[r] == count).map(|r| Rank::from_u8(r as u8))
+}
+
+/// Up to `n` highest ranks set in `mask`, padded the same way as `HandValue::ranks`
+fn top_ranks(mask: u16, n: usize) -> [Rank; 5] {
+    let mut ranks = [Rank::Two; 5];
+    let mut found = 0;
+    for r in (0..13u8).rev() {
+        if found == n {
+            break;
+        }
+        if mask & (1 << r) != 0 {
+            ranks[found] = Rank::from_u8(r);
+            found += 1;
+        }
+    }
+    ranks
+}
+
+/// Highest card of the best straight in `mask`, if any
+fn straight_high(mask: u16) -> Option<Rank> {
+    // Shift ranks up one bit so the ace can also play low (A-2-3-4-5)
+    let extended = (mask << 1) | ((mask >> 12) & 1);
+    (4..=13u8).rev().find_map(|high| {
+        let window = 0b11111u16 << (high - 4);
+        (extended & window == window).then(|| Rank::from_u8(high - 1))
+    })
+}
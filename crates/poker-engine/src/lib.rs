@@ -0,0 +1,20 @@
+//! Texas Hold'em rules engine.
+//!
+//! Deck handling, dealing, the betting state machine, hand evaluation and pot
+//! splitting live here so the on-chain program and off-chain services run the
+//! exact same rules. The crate is `no_std` and does not allocate.
+#![cfg_attr(not(test), no_std)]
+
+pub mod card;
+pub mod deck;
+pub mod error;
+pub mod hand;
+pub mod pot;
+pub mod table;
+
+pub use card::*;
+pub use deck::*;
+pub use error::*;
+pub use hand::*;
+pub use pot::*;
+pub use table::*;
@@ -0,0 +1,172 @@
+use crate::error::EngineError;
+use crate::hand::HandValue;
+use crate::table::MAX_SEATS;
+
+/// A main or side pot and the seats that can win it
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Pot {
+    pub amount: u64,
+    /// Bit `n` is set when seat `n` is eligible
+    pub eligible: u16,
+}
+
+impl Pot {
+    pub fn is_eligible(&self, seat: u8) -> bool {
+        self.eligible & (1 << seat) != 0
+    }
+
+    pub fn eligible_count(&self) -> u32 {
+        self.eligible.count_ones()
+    }
+}
+
+/// Main pot followed by side pots in order of increasing contribution level
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Pots {
+    pots: [Pot; MAX_SEATS],
+    len: usize,
+}
+
+impl Pots {
+    pub fn as_slice(&self) -> &[Pot] {
+        &self.pots[..self.len]
+    }
+
+    pub fn total(&self) -> u64 {
+        self.as_slice().iter().map(|p| p.amount).sum()
+    }
+
+    /// Split every pot in two for running the board twice; the first half
+    /// keeps any odd chip
+    pub fn halve(&self) -> (Pots, Pots) {
+        let mut first = *self;
+        let mut second = *self;
+        for (a, b) in first.pots[..self.len].iter_mut().zip(&mut second.pots[..self.len]) {
+            b.amount = a.amount / 2;
+            a.amount -= b.amount;
+        }
+        (first, second)
+    }
+
+    /// Take `bps` basis points out of every pot, rounding down; returns the
+    /// chips taken
+    pub fn take_share(&mut self, bps: u16) -> Result<u64, EngineError> {
+        if bps > 10_000 {
+            return Err(EngineError::InvalidAction);
+        }
+        let mut taken = 0u64;
+        for pot in self.pots[..self.len].iter_mut() {
+            let share = (u128::from(pot.amount) * u128::from(bps) / 10_000) as u64;
+            pot.amount -= share;
+            taken = taken.checked_add(share).ok_or(EngineError::MathOverflow)?;
+        }
+        Ok(taken)
+    }
+
+    /// Add chips that nobody still in the hand contributed (e.g. a departed player's bet)
+    pub fn add_dead_money(&mut self, amount: u64) -> Result<(), EngineError> {
+        let main = self.pots[..self.len].first_mut().ok_or(EngineError::InvalidGameState)?;
+        main.amount = main.amount.checked_add(amount).ok_or(EngineError::MathOverflow)?;
+        Ok(())
+    }
+}
+
+/// Split hand contributions into a main pot and side pots.
+///
+/// Every distinct contribution level of a live (not folded) seat closes a pot.
+/// Chips folded players put in above the highest live level go to the last pot,
+/// or to a pot shared by every live seat if none of them contributed.
+pub fn build_pots(
+    contributions: &[u64; MAX_SEATS],
+    live: &[bool; MAX_SEATS],
+) -> Result<Pots, EngineError> {
+    let mut pots = Pots::default();
+    let mut previous_level = 0u64;
+
+    loop {
+        let level = (0..MAX_SEATS)
+            .filter(|&s| live[s] && contributions[s] > previous_level)
+            .map(|s| contributions[s])
+            .min();
+        let Some(level) = level else { break };
+
+        let mut pot = Pot::default();
+        for seat in 0..MAX_SEATS {
+            let layer = contributions[seat].min(level) - contributions[seat].min(previous_level);
+            pot.amount = pot.amount.checked_add(layer).ok_or(EngineError::MathOverflow)?;
+            if live[seat] && contributions[seat] >= level {
+                pot.eligible |= 1 << seat;
+            }
+        }
+        pots.pots[pots.len] = pot;
+        pots.len += 1;
+        previous_level = level;
+    }
+
+    // Every live seat put in nothing (e.g. both blinds left before anyone
+    // acted), so the dead money goes to a single pot they all share
+    if pots.len == 0 {
+        let eligible = (0..MAX_SEATS).filter(|&s| live[s]).fold(0u16, |mask, s| mask | (1 << s));
+        if eligible != 0 {
+            pots.pots[0] = Pot { amount: 0, eligible };
+            pots.len = 1;
+        }
+    }
+
+    let excess = contributions
+        .iter()
+        .try_fold(0u64, |acc, &c| acc.checked_add(c.saturating_sub(previous_level)))
+        .ok_or(EngineError::MathOverflow)?;
+    if excess > 0 {
+        let last = pots.pots[..pots.len].last_mut().ok_or(EngineError::InvalidGameState)?;
+        last.amount = last.amount.checked_add(excess).ok_or(EngineError::MathOverflow)?;
+    }
+
+    Ok(pots)
+}
+
+/// Award each pot to its best eligible hand.
+///
+/// A pot with a single eligible seat goes to that seat without needing a hand.
+/// Split pots are divided evenly; odd chips go one at a time to the winners
+/// closest to the left of `button`.
+pub fn award(
+    pots: &Pots,
+    hands: &[Option<HandValue>; MAX_SEATS],
+    button: u8,
+) -> Result<[u64; MAX_SEATS], EngineError> {
+    let mut payouts = [0u64; MAX_SEATS];
+
+    for pot in pots.as_slice() {
+        let winners = if pot.eligible_count() == 1 {
+            pot.eligible
+        } else {
+            let best = (0..MAX_SEATS as u8)
+                .filter(|&s| pot.is_eligible(s))
+                .filter_map(|s| hands[s as usize])
+                .max()
+                .ok_or(EngineError::InvalidGameState)?;
+            (0..MAX_SEATS as u8)
+                .filter(|&s| pot.is_eligible(s) && hands[s as usize] == Some(best))
+                .fold(0u16, |mask, s| mask | (1 << s))
+        };
+
+        let winner_count = winners.count_ones() as u64;
+        let share = pot.amount / winner_count;
+        let mut odd_chips = pot.amount % winner_count;
+        for offset in 1..=MAX_SEATS {
+            let seat = (button as usize + offset) % MAX_SEATS;
+            if winners & (1 << seat) == 0 {
+                continue;
+            }
+            let mut amount = share;
+            if odd_chips > 0 {
+                amount += 1;
+                odd_chips -= 1;
+            }
+            payouts[seat] = payouts[seat].checked_add(amount).ok_or(EngineError::MathOverflow)?;
+        }
+    }
+
+    Ok(payouts)
+}
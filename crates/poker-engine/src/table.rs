@@ -0,0 +1,928 @@
+use crate::card::{Card, Rank};
+use crate::deck::Deck;
+use crate::error::EngineError;
+use crate::hand::{evaluate, HandCategory, HandValue};
+use crate::pot::{award, build_pots, Pots};
+
+pub const MAX_SEATS: usize = 9;
+pub const HOLE_CARDS: usize = 2;
+pub const BOARD_CARDS: usize = 5;
+
+/// Weakest hand that qualifies for the bad-beat jackpot by losing: four eights
+pub const BAD_BEAT_MINIMUM: HandValue = HandValue {
+    category: HandCategory::FourOfAKind,
+    ranks: [Rank::Eight, Rank::Two, Rank::Two, Rank::Two, Rank::Two],
+};
+
+/// Game state
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GameState {
+    Waiting = 0,
+    Starting = 1,
+    PreFlop = 2,
+    Flop = 3,
+    Turn = 4,
+    River = 5,
+    Showdown = 6,
+    Finished = 7,
+}
+
+impl GameState {
+    /// Whether players are currently betting
+    pub fn is_betting(&self) -> bool {
+        matches!(
+            self,
+            GameState::PreFlop | GameState::Flop | GameState::Turn | GameState::River
+        )
+    }
+}
+
+/// Betting round
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Round {
+    PreFlop = 0,
+    Flop = 1,
+    Turn = 2,
+    River = 3,
+}
+
+/// Player action type
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ActionType {
+    Fold = 0,
+    Check = 1,
+    Call = 2,
+    Raise = 3,
+    AllIn = 4,
+}
+
+/// Player state at a table
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Player {
+    /// Opaque identity of the player (the wallet pubkey on-chain)
+    pub id: [u8; 32],
+    pub stack: u64,
+    /// Chips committed to the pot during the current hand
+    pub bet: u64,
+    pub hole_cards: [Option<Card>; HOLE_CARDS],
+    pub is_all_in: bool,
+    pub has_folded: bool,
+    pub has_acted: bool,
+    pub last_action: Option<ActionType>,
+    /// Agrees to run the board twice if the hand is all-in before the river
+    pub run_twice: bool,
+    /// Mucks instead of showing a losing hand at showdown. Unlike the rest of
+    /// the player state this carries over between hands.
+    pub muck: bool,
+    /// This hand's show-or-muck decision, overriding `muck`
+    pub show: Option<bool>,
+}
+
+impl Player {
+    pub fn new(id: [u8; 32], stack: u64) -> Self {
+        Self {
+            id,
+            stack,
+            bet: 0,
+            hole_cards: [None; HOLE_CARDS],
+            is_all_in: false,
+            has_folded: false,
+            has_acted: false,
+            last_action: None,
+            run_twice: false,
+            muck: false,
+            show: None,
+        }
+    }
+
+    /// Dealt into the current hand and not folded
+    pub fn in_hand(&self) -> bool {
+        self.hole_cards[0].is_some() && !self.has_folded
+    }
+
+    /// Still able to make betting decisions this hand
+    pub fn can_act(&self) -> bool {
+        self.in_hand() && !self.is_all_in
+    }
+
+    fn reset_hand(&mut self) {
+        self.bet = 0;
+        self.hole_cards = [None; HOLE_CARDS];
+        self.is_all_in = false;
+        self.has_folded = false;
+        self.has_acted = false;
+        self.last_action = None;
+        self.run_twice = false;
+        self.show = None;
+    }
+}
+
+/// Result of a successful `Table::act`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ActionOutcome {
+    /// Chips moved from the player's stack into the pot
+    pub amount: u64,
+    /// The action closed the betting round and the table moved on
+    pub round_complete: bool,
+    /// Bet handed back because the action ended the betting with it unmatched
+    pub uncalled: Option<UncalledBet>,
+}
+
+/// Part of a bet nobody else matched, returned to the bettor's stack once
+/// betting is over
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UncalledBet {
+    pub seat: u8,
+    pub amount: u64,
+}
+
+/// Hands revealed by `Table::showdown`, indexed by seat
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ShowdownResult {
+    pub hands: [Option<HandValue>; MAX_SEATS],
+    /// Hands on the second board when the hand was run twice
+    pub second_hands: Option<[Option<HandValue>; MAX_SEATS]>,
+    /// Seats still in the hand in the order they show down: the last
+    /// aggressor of the final betting round first, else the first seat left
+    /// of the button, then clockwise
+    pub order: [Option<u8>; MAX_SEATS],
+    /// Seats whose hole cards are turned face up. The first to show, anyone
+    /// who could still win against the hands shown before them and anyone
+    /// paid from the pot must show; everyone else follows their show-or-muck
+    /// decision.
+    pub shown: [bool; MAX_SEATS],
+}
+
+/// Chips awarded by `Table::settle`, indexed by seat
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Settlement {
+    pub pots: Pots,
+    pub payouts: [u64; MAX_SEATS],
+    /// Chips taken out of the pots before they were awarded, see `Table::settle_with_drop`
+    pub drop: u64,
+}
+
+/// Seats in a hand that qualifies for the bad-beat jackpot
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BadBeat {
+    pub loser: u8,
+    pub winner: u8,
+}
+
+/// Poker table state
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Table {
+    pub small_blind: u64,
+    pub big_blind: u64,
+    pub max_players: u8,
+    pub game_state: GameState,
+    pub dealer_position: u8,
+    pub small_blind_position: u8,
+    pub big_blind_position: u8,
+    pub current_player: Option<u8>,
+    /// Last player to bet or raise in the current betting round
+    pub last_aggressor: Option<u8>,
+    pub pot: u64,
+    pub community_cards: [Option<Card>; BOARD_CARDS],
+    /// Second runout of the board, dealt when every player left agreed to run it twice
+    pub second_board: Option<[Option<Card>; BOARD_CARDS]>,
+    pub round: Round,
+    pub last_raise_amount: u64,
+    pub min_raise: u64,
+    pub players: [Option<Player>; MAX_SEATS],
+    pub player_count: u8,
+    /// Hands started at this table; zero until the first deal
+    pub hand_number: u64,
+    /// Seed the current hand's deck was shuffled with. Later streets are
+    /// dealt from it, so whoever can read it knows every card.
+    pub deck_seed: [u8; 32],
+    /// Index of the next undealt card in the shuffled deck
+    pub deck_position: u8,
+}
+
+impl Table {
+    pub fn new(small_blind: u64, big_blind: u64, max_players: u8) -> Self {
+        Self {
+            small_blind,
+            big_blind,
+            max_players,
+            game_state: GameState::Waiting,
+            dealer_position: 0,
+            small_blind_position: 0,
+            big_blind_position: 0,
+            current_player: None,
+            last_aggressor: None,
+            pot: 0,
+            community_cards: [None; BOARD_CARDS],
+            second_board: None,
+            round: Round::PreFlop,
+            last_raise_amount: 0,
+            min_raise: big_blind,
+            players: [None; MAX_SEATS],
+            player_count: 0,
+            hand_number: 0,
+            deck_seed: [0; 32],
+            deck_position: 0,
+        }
+    }
+
+    pub fn player(&self, seat: u8) -> Option<&Player> {
+        self.players.get(seat as usize).and_then(Option::as_ref)
+    }
+
+    pub fn player_mut(&mut self, seat: u8) -> Option<&mut Player> {
+        self.players.get_mut(seat as usize).and_then(Option::as_mut)
+    }
+
+    pub fn find_seat(&self, id: &[u8; 32]) -> Option<u8> {
+        self.players
+            .iter()
+            .position(|p| p.as_ref().is_some_and(|p| &p.id == id))
+            .map(|seat| seat as u8)
+    }
+
+    /// Highest contribution of any player still in the hand
+    pub fn current_bet(&self) -> u64 {
+        self.players
+            .iter()
+            .flatten()
+            .filter(|p| p.in_hand())
+            .map(|p| p.bet)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Chips held at the table: every stack plus the pot
+    pub fn chips_in_play(&self) -> Option<u64> {
+        self.players
+            .iter()
+            .flatten()
+            .try_fold(self.pot, |acc, p| acc.checked_add(p.stack))
+    }
+
+    /// Structural invariants that hold after every successful transition
+    pub fn check_invariants(&self) -> Result<(), &'static str> {
+        let seated = self.players.iter().flatten().count();
+        if self.player_count as usize != seated {
+            return Err("player_count does not match seated players");
+        }
+        if self.player_count > self.max_players {
+            return Err("player_count above max_players");
+        }
+        // Players leaving before a finished hand is settled leave their bets behind
+        let bets = self.total_bets().map_err(|_| "player bets overflow")?;
+        let pot_ok = match self.game_state {
+            GameState::Finished => bets <= self.pot,
+            _ => bets == self.pot,
+        };
+        if !pot_ok {
+            return Err("pot does not match player bets");
+        }
+
+        if self.game_state.is_betting() {
+            let board = match self.round {
+                Round::PreFlop => 0,
+                Round::Flop => 3,
+                Round::Turn => 4,
+                Round::River => 5,
+            };
+            if self.community_cards.iter().flatten().count() != board {
+                return Err("community cards do not match the betting round");
+            }
+            let can_act = self
+                .current_player
+                .and_then(|seat| self.player(seat))
+                .is_some_and(|p| p.can_act());
+            if !can_act {
+                return Err("current player cannot act");
+            }
+        } else if self.current_player.is_some() {
+            return Err("current player set outside a betting round");
+        }
+        Ok(())
+    }
+
+    /// Seat a new player. Only allowed between hands.
+    pub fn seat_player(&mut self, seat: u8, id: [u8; 32], stack: u64) -> Result<(), EngineError> {
+        if seat as usize >= MAX_SEATS || seat >= self.max_players {
+            return Err(EngineError::InvalidSeat);
+        }
+        if self.game_state != GameState::Waiting {
+            return Err(EngineError::GameInProgress);
+        }
+        if self.player_count >= self.max_players {
+            return Err(EngineError::TableFull);
+        }
+        if self.players[seat as usize].is_some() {
+            return Err(EngineError::SeatOccupied);
+        }
+        if self.find_seat(&id).is_some() {
+            return Err(EngineError::AlreadySeated);
+        }
+
+        self.players[seat as usize] = Some(Player::new(id, stack));
+        self.player_count += 1;
+        Ok(())
+    }
+
+    /// Remove a player, returning their final state. Only allowed between hands.
+    ///
+    /// Leaving while a finished hand awaits settlement forfeits any chips
+    /// already in the pot.
+    pub fn unseat_player(&mut self, seat: u8) -> Result<Player, EngineError> {
+        if !matches!(self.game_state, GameState::Waiting | GameState::Finished) {
+            return Err(EngineError::CannotLeaveDuringGame);
+        }
+        let player = self
+            .players
+            .get_mut(seat as usize)
+            .and_then(Option::take)
+            .ok_or(EngineError::PlayerNotFound)?;
+        self.player_count = self.player_count.checked_sub(1).ok_or(EngineError::MathOverflow)?;
+        Ok(player)
+    }
+
+    /// Move the button, post blinds, shuffle with `deck_seed` and deal hole cards
+    ///
+    /// Returns the uncalled part of a blind when the blinds alone put
+    /// everyone all-in.
+    pub fn start_hand(&mut self, deck_seed: [u8; 32]) -> Result<Option<UncalledBet>, EngineError> {
+        if self.game_state != GameState::Waiting {
+            return Err(EngineError::InvalidGameState);
+        }
+        let funded = self.players.iter().flatten().filter(|p| p.stack > 0).count();
+        if funded < 2 {
+            return Err(EngineError::NotEnoughPlayers);
+        }
+
+        self.reset_hand();
+        let has_chips = |p: &Player| p.stack > 0;
+
+        // First hand starts at the lowest funded seat, later hands rotate clockwise
+        let from = if self.hand_number == 0 { MAX_SEATS as u8 - 1 } else { self.dealer_position };
+        self.dealer_position = self.next_seat(from, has_chips).ok_or(EngineError::NotEnoughPlayers)?;
+        // Heads-up the button posts the small blind
+        self.small_blind_position = if funded == 2 {
+            self.dealer_position
+        } else {
+            self.next_seat(self.dealer_position, has_chips).ok_or(EngineError::NotEnoughPlayers)?
+        };
+        self.big_blind_position = self
+            .next_seat(self.small_blind_position, has_chips)
+            .ok_or(EngineError::NotEnoughPlayers)?;
+        self.hand_number = self.hand_number.checked_add(1).ok_or(EngineError::MathOverflow)?;
+
+        // Deal one card at a time, starting left of the button
+        let deck = Deck::shuffled(&deck_seed);
+        let mut position = 0usize;
+        for card in 0..HOLE_CARDS {
+            for offset in 1..=MAX_SEATS {
+                let seat = (self.dealer_position as usize + offset) % MAX_SEATS;
+                if let Some(player) = self.players[seat].as_mut().filter(|p| p.stack > 0) {
+                    player.hole_cards[card] = deck.card(position);
+                    position += 1;
+                }
+            }
+        }
+        self.deck_seed = deck_seed;
+        self.deck_position = position as u8;
+
+        self.post_blind(self.small_blind_position, self.small_blind)?;
+        self.post_blind(self.big_blind_position, self.big_blind)?;
+        self.pot = self.total_bets()?;
+        self.last_raise_amount = self.big_blind;
+        self.min_raise = self.big_blind;
+        self.round = Round::PreFlop;
+        self.game_state = GameState::PreFlop;
+
+        // First to act is the player after the big blind
+        let (_, uncalled) = self.next_turn(self.big_blind_position)?;
+        Ok(uncalled)
+    }
+
+    /// Apply a betting decision for the player at `seat`.
+    ///
+    /// `amount` is only used by `Raise` and is the increase over the amount to call.
+    pub fn act(
+        &mut self,
+        seat: u8,
+        action_type: ActionType,
+        amount: Option<u64>,
+    ) -> Result<ActionOutcome, EngineError> {
+        if !self.game_state.is_betting() {
+            return Err(EngineError::InvalidGameState);
+        }
+        let current_bet = self.current_bet();
+        let min_raise = self.min_raise;
+
+        let player = self.player_mut(seat).ok_or(EngineError::PlayerNotFound)?;
+        if !player.can_act() {
+            return Err(EngineError::NotPlayersTurn);
+        }
+        if player.has_acted && player.bet >= current_bet {
+            return Err(EngineError::AlreadyActed);
+        }
+        if self.current_player != Some(seat) {
+            return Err(EngineError::NotPlayersTurn);
+        }
+
+        let player = self.player_mut(seat).ok_or(EngineError::PlayerNotFound)?;
+        let to_call = current_bet.saturating_sub(player.bet);
+        let chips = match action_type {
+            ActionType::Fold => {
+                player.has_folded = true;
+                0
+            }
+            ActionType::Check => {
+                if to_call != 0 {
+                    return Err(EngineError::InvalidAction);
+                }
+                0
+            }
+            ActionType::Call => {
+                if to_call == 0 {
+                    return Err(EngineError::InvalidAction);
+                }
+                player.stack.min(to_call)
+            }
+            ActionType::Raise => {
+                let raise_amount = amount.ok_or(EngineError::InvalidAction)?;
+                if raise_amount < min_raise {
+                    return Err(EngineError::RaiseTooSmall);
+                }
+                let total = to_call.checked_add(raise_amount).ok_or(EngineError::MathOverflow)?;
+                if total > player.stack {
+                    return Err(EngineError::InsufficientFunds);
+                }
+                total
+            }
+            ActionType::AllIn => {
+                if player.stack == 0 {
+                    return Err(EngineError::InvalidAction);
+                }
+                player.stack
+            }
+        };
+
+        player.stack = player.stack.checked_sub(chips).ok_or(EngineError::MathOverflow)?;
+        player.bet = player.bet.checked_add(chips).ok_or(EngineError::MathOverflow)?;
+        if chips > 0 && player.stack == 0 {
+            player.is_all_in = true;
+        }
+        player.has_acted = true;
+        player.last_action = Some(action_type);
+        let new_bet = player.bet;
+
+        if new_bet > current_bet {
+            self.last_aggressor = Some(seat);
+            // Only a full raise changes the minimum re-raise, but any increase
+            // gives everyone else another decision
+            let raised_by = new_bet - current_bet;
+            if raised_by >= min_raise {
+                self.last_raise_amount = raised_by;
+                self.min_raise = raised_by;
+            }
+            for (other, p) in self.players.iter_mut().enumerate() {
+                if let Some(p) = p.as_mut().filter(|p| p.can_act() && other != seat as usize) {
+                    p.has_acted = false;
+                }
+            }
+        }
+
+        self.pot = self.total_bets()?;
+        let (round_complete, uncalled) = self.next_turn(seat)?;
+
+        Ok(ActionOutcome { amount: chips, round_complete, uncalled })
+    }
+
+    /// Fold the player at `seat` whether or not it is their turn, e.g. because
+    /// they left the table. All-in players have nothing left to decide and stay
+    /// in the hand.
+    pub fn force_fold(&mut self, seat: u8) -> Result<ActionOutcome, EngineError> {
+        if !self.game_state.is_betting() {
+            return Err(EngineError::InvalidGameState);
+        }
+        if self.current_player == Some(seat) {
+            return self.act(seat, ActionType::Fold, None);
+        }
+        let player = self.player_mut(seat).ok_or(EngineError::PlayerNotFound)?;
+        if !player.can_act() {
+            return Ok(ActionOutcome { amount: 0, round_complete: false, uncalled: None });
+        }
+        player.has_folded = true;
+        player.has_acted = true;
+        player.last_action = Some(ActionType::Fold);
+
+        // The fold may lower the bet to match or end the round; otherwise the
+        // player to act keeps the turn
+        let current_bet = self.current_bet();
+        let current = self.current_player.ok_or(EngineError::InvalidGameState)?;
+        let still_to_act = self
+            .player(current)
+            .is_some_and(|p| p.can_act() && (!p.has_acted || p.bet < current_bet));
+        let (round_complete, uncalled) = if self.betting_complete() || !still_to_act {
+            self.next_turn(current)?
+        } else {
+            (false, None)
+        };
+        Ok(ActionOutcome { amount: 0, round_complete, uncalled })
+    }
+
+    /// Record whether the player at `seat` agrees to run the board twice.
+    /// Takes effect if every player left agrees by the time betting is over.
+    pub fn vote_run_twice(&mut self, seat: u8, agree: bool) -> Result<(), EngineError> {
+        if !self.game_state.is_betting() {
+            return Err(EngineError::InvalidGameState);
+        }
+        let player = self.player_mut(seat).ok_or(EngineError::PlayerNotFound)?;
+        if !player.in_hand() {
+            return Err(EngineError::InvalidAction);
+        }
+        player.run_twice = agree;
+        Ok(())
+    }
+
+    /// Record whether the player at `seat` shows or mucks this hand should
+    /// they lose at showdown. Winning hands are always shown.
+    pub fn show_or_muck(&mut self, seat: u8, show: bool) -> Result<(), EngineError> {
+        if !self.game_state.is_betting() && self.game_state != GameState::Showdown {
+            return Err(EngineError::InvalidGameState);
+        }
+        let player = self.player_mut(seat).ok_or(EngineError::PlayerNotFound)?;
+        if !player.in_hand() {
+            return Err(EngineError::InvalidAction);
+        }
+        player.show = Some(show);
+        Ok(())
+    }
+
+    /// Evaluate every remaining hand and finish the hand
+    pub fn showdown(&mut self) -> Result<ShowdownResult, EngineError> {
+        if self.game_state != GameState::Showdown {
+            return Err(EngineError::InvalidGameState);
+        }
+        let hands = self.hand_values();
+        let second_hands = self.second_board.map(|board| self.hand_values_on(&board));
+        self.current_player = None;
+        self.game_state = GameState::Finished;
+
+        let payouts = self.payouts()?.payouts;
+        let first = self
+            .last_aggressor
+            .filter(|&seat| self.player(seat).is_some_and(Player::in_hand))
+            .or_else(|| self.next_seat(self.dealer_position, Player::in_hand))
+            .ok_or(EngineError::InvalidGameState)?;
+
+        let mut order = [None; MAX_SEATS];
+        let mut shown = [false; MAX_SEATS];
+        let mut best: Option<HandValue> = None;
+        let mut best_second: Option<HandValue> = None;
+        let seats = (0..MAX_SEATS).map(|offset| (first as usize + offset) % MAX_SEATS);
+        for (slot, seat) in seats.filter(|&seat| hands[seat].is_some()).enumerate() {
+            let player = self.players[seat].as_ref().ok_or(EngineError::PlayerNotFound)?;
+            let second = second_hands.and_then(|h| h[seat]);
+            let could_win = hands[seat] >= best || (second.is_some() && second >= best_second);
+            order[slot] = Some(seat as u8);
+            shown[seat] = best.is_none() || could_win || payouts[seat] > 0 || player.show.unwrap_or(!player.muck);
+            if shown[seat] {
+                best = best.max(hands[seat]);
+                best_second = best_second.max(second);
+            }
+        }
+        Ok(ShowdownResult { hands, second_hands, order, shown })
+    }
+
+    /// Split the pot between the winners, credit their stacks and reset for the next hand
+    pub fn settle(&mut self) -> Result<Settlement, EngineError> {
+        self.settle_with_drop(0)
+    }
+
+    /// Like `settle`, but first takes `drop_bps` basis points of every pot
+    /// (e.g. for a jackpot) if the hand saw a flop
+    pub fn settle_with_drop(&mut self, drop_bps: u16) -> Result<Settlement, EngineError> {
+        let settlement = self.payouts_after_drop(drop_bps)?;
+        for (seat, payout) in settlement.payouts.iter().enumerate() {
+            if let Some(player) = self.players[seat].as_mut() {
+                player.stack = player.stack.checked_add(*payout).ok_or(EngineError::MathOverflow)?;
+            }
+        }
+        self.reset_hand();
+
+        Ok(settlement)
+    }
+
+    /// What `settle` would pay each seat, without paying it
+    pub fn payouts(&self) -> Result<Settlement, EngineError> {
+        self.payouts_after_drop(0)
+    }
+
+    /// What `settle_with_drop` would pay each seat, without paying it
+    pub fn payouts_after_drop(&self, drop_bps: u16) -> Result<Settlement, EngineError> {
+        if self.game_state != GameState::Finished {
+            return Err(EngineError::InvalidGameState);
+        }
+
+        let mut contributions = [0u64; MAX_SEATS];
+        let mut live = [false; MAX_SEATS];
+        for (seat, player) in self.players.iter().enumerate() {
+            if let Some(player) = player {
+                contributions[seat] = player.bet;
+                live[seat] = player.in_hand();
+            }
+        }
+
+        let mut pots = build_pots(&contributions, &live)?;
+        let dead_money = self.pot.checked_sub(pots.total()).ok_or(EngineError::MathOverflow)?;
+        if dead_money > 0 {
+            pots.add_dead_money(dead_money)?;
+        }
+        // No flop, no drop
+        let drop = if self.community_cards[2].is_some() { pots.take_share(drop_bps)? } else { 0 };
+        let payouts = match self.second_board {
+            Some(board) => {
+                // Each runout wins half of every pot
+                let (first, second) = pots.halve();
+                let mut payouts = award(&first, &self.hand_values(), self.dealer_position)?;
+                let second = award(&second, &self.hand_values_on(&board), self.dealer_position)?;
+                for (payout, extra) in payouts.iter_mut().zip(second) {
+                    *payout = payout.checked_add(extra).ok_or(EngineError::MathOverflow)?;
+                }
+                payouts
+            }
+            None => award(&pots, &self.hand_values(), self.dealer_position)?,
+        };
+
+        Ok(Settlement { pots, payouts, drop })
+    }
+
+    /// The bad beat in a finished hand, if any: the best losing hand at
+    /// showdown is at least `BAD_BEAT_MINIMUM` and uses both hole cards.
+    /// Ties go to the first seat left of the button, and hands run twice
+    /// never qualify.
+    pub fn bad_beat(&self) -> Option<BadBeat> {
+        if self.game_state != GameState::Finished || self.second_board.is_some() {
+            return None;
+        }
+        let hands = self.hand_values();
+        let best = hands.iter().flatten().max().copied()?;
+        let seats = || (1..=MAX_SEATS).map(|offset| (self.dealer_position as usize + offset) % MAX_SEATS);
+        let winner = seats().find(|&seat| hands[seat] == Some(best))?;
+        let (loser, value) = seats()
+            .filter_map(|seat| hands[seat].filter(|&hand| hand < best).map(|hand| (seat, hand)))
+            .fold(None, |top: Option<(usize, HandValue)>, (seat, hand)| match top {
+                Some((_, value)) if value >= hand => top,
+                _ => Some((seat, hand)),
+            })?;
+        if value < BAD_BEAT_MINIMUM || !self.plays_both_hole_cards(loser, value) {
+            return None;
+        }
+        Some(BadBeat { loser: loser as u8, winner: winner as u8 })
+    }
+
+    /// The full board as it would have been dealt, for showing the cards a
+    /// finished hand never reached. Only the community cards are dealt for
+    /// real; the rest is for display.
+    pub fn rabbit_hunt(&self) -> Result<[Option<Card>; BOARD_CARDS], EngineError> {
+        if self.game_state != GameState::Finished {
+            return Err(EngineError::InvalidGameState);
+        }
+        let dealt = self.community_cards.iter().flatten().count();
+        Ok(self.runout(dealt).0)
+    }
+
+    /// Best hand of every player still in the hand, indexed by seat
+    pub fn hand_values(&self) -> [Option<HandValue>; MAX_SEATS] {
+        self.hand_values_on(&self.community_cards)
+    }
+
+    /// Like `hand_values`, but against `board` instead of the community cards
+    pub fn hand_values_on(&self, board: &[Option<Card>; BOARD_CARDS]) -> [Option<HandValue>; MAX_SEATS] {
+        let mut hands = [None; MAX_SEATS];
+        for (seat, player) in self.players.iter().enumerate() {
+            let Some(player) = player.as_ref().filter(|p| p.in_hand()) else { continue };
+            let mut cards = [Card::from_u8(0); HOLE_CARDS + BOARD_CARDS];
+            let mut len = 0;
+            for card in player.hole_cards.iter().chain(board.iter()).flatten() {
+                cards[len] = *card;
+                len += 1;
+            }
+            hands[seat] = Some(evaluate(&cards[..len]));
+        }
+        hands
+    }
+
+    /// Whether the hand at `seat` is worse with either hole card left out
+    fn plays_both_hole_cards(&self, seat: usize, value: HandValue) -> bool {
+        let Some(player) = self.players[seat].as_ref() else { return false };
+        player.hole_cards.iter().flatten().all(|&left_out| {
+            let mut cards = [Card::from_u8(0); HOLE_CARDS + BOARD_CARDS];
+            let mut len = 0;
+            let hole = player.hole_cards.iter().flatten().filter(|&&card| card != left_out);
+            for card in hole.chain(self.community_cards.iter().flatten()) {
+                cards[len] = *card;
+                len += 1;
+            }
+            evaluate(&cards[..len]) < value
+        })
+    }
+
+    fn reset_hand(&mut self) {
+        for player in self.players.iter_mut().flatten() {
+            player.reset_hand();
+        }
+        self.game_state = GameState::Waiting;
+        self.round = Round::PreFlop;
+        self.current_player = None;
+        self.last_aggressor = None;
+        self.pot = 0;
+        self.community_cards = [None; BOARD_CARDS];
+        self.second_board = None;
+        self.last_raise_amount = 0;
+        self.min_raise = self.big_blind;
+        self.deck_seed = [0; 32];
+        self.deck_position = 0;
+    }
+
+    fn total_bets(&self) -> Result<u64, EngineError> {
+        self.players
+            .iter()
+            .flatten()
+            .try_fold(0u64, |acc, p| acc.checked_add(p.bet))
+            .ok_or(EngineError::MathOverflow)
+    }
+
+    fn post_blind(&mut self, seat: u8, blind: u64) -> Result<(), EngineError> {
+        let player = self.player_mut(seat).ok_or(EngineError::PlayerNotFound)?;
+        let amount = player.stack.min(blind);
+        player.stack = player.stack.checked_sub(amount).ok_or(EngineError::MathOverflow)?;
+        player.bet = player.bet.checked_add(amount).ok_or(EngineError::MathOverflow)?;
+        if player.stack == 0 {
+            player.is_all_in = true;
+        }
+        Ok(())
+    }
+
+    /// First occupied seat clockwise after `from` (wrapping back to `from`) matching `pred`
+    fn next_seat(&self, from: u8, pred: impl Fn(&Player) -> bool) -> Option<u8> {
+        (1..=MAX_SEATS)
+            .map(|offset| (from as usize + offset) % MAX_SEATS)
+            .find(|&seat| self.players[seat].as_ref().is_some_and(&pred))
+            .map(|seat| seat as u8)
+    }
+
+    /// The betting round is over once every player who can still act has
+    /// matched the current bet and had a chance to act on it
+    fn betting_complete(&self) -> bool {
+        let current_bet = self.current_bet();
+        let actors = self.players.iter().flatten().filter(|p| p.can_act()).count();
+        self.players
+            .iter()
+            .flatten()
+            .filter(|p| p.can_act())
+            .all(|p| p.bet == current_bet && (p.has_acted || actors <= 1))
+    }
+
+    /// Pass the turn to the next player after `from`, dealing further streets
+    /// while no betting decisions remain. Returns whether a round ended, and
+    /// the bet returned if betting is over with one left unmatched.
+    fn next_turn(&mut self, from: u8) -> Result<(bool, Option<UncalledBet>), EngineError> {
+        // Once everyone else has folded the hand is over, with no more
+        // streets dealt and nothing to show
+        if self.players.iter().flatten().filter(|p| p.in_hand()).count() <= 1 {
+            self.game_state = GameState::Finished;
+            self.current_player = None;
+            return Ok((true, self.return_uncalled_bet()?));
+        }
+        let mut from = from;
+        let mut round_complete = false;
+        let dealt = self.community_cards.iter().flatten().count();
+        let run_twice = self.betting_complete() && self.wants_run_twice();
+        while self.game_state.is_betting() && self.betting_complete() {
+            self.advance_round();
+            round_complete = true;
+            from = self.dealer_position;
+        }
+        if run_twice && dealt < BOARD_CARDS && self.game_state == GameState::Showdown {
+            self.deal_second_board(dealt);
+        }
+        // Streets dealt with nobody left to bet keep the last aggressor
+        if round_complete && self.game_state.is_betting() {
+            self.last_aggressor = None;
+        }
+
+        if !self.game_state.is_betting() {
+            self.current_player = None;
+            return Ok((round_complete, self.return_uncalled_bet()?));
+        }
+        let current_bet = self.current_bet();
+        self.current_player = self.next_seat(from, |p| p.can_act() && (!p.has_acted || p.bet < current_bet));
+        Ok((round_complete, None))
+    }
+
+    /// Give the largest bet back down to the next largest, since nobody can
+    /// match it any more. A folded player's bet stays in the pot.
+    fn return_uncalled_bet(&mut self) -> Result<Option<UncalledBet>, EngineError> {
+        let Some((seat, top)) = self
+            .players
+            .iter()
+            .enumerate()
+            .filter_map(|(seat, p)| p.as_ref().filter(|p| p.in_hand()).map(|p| (seat, p.bet)))
+            .max_by_key(|&(_, bet)| bet)
+        else {
+            return Ok(None);
+        };
+        let matched = self
+            .players
+            .iter()
+            .enumerate()
+            .filter(|&(other, _)| other != seat)
+            .filter_map(|(_, p)| p.as_ref().map(|p| p.bet))
+            .max()
+            .unwrap_or(0);
+        let amount = match top.checked_sub(matched) {
+            Some(amount) if amount > 0 => amount,
+            _ => return Ok(None),
+        };
+
+        let player = self.players[seat].as_mut().ok_or(EngineError::PlayerNotFound)?;
+        player.bet -= amount;
+        player.stack = player.stack.checked_add(amount).ok_or(EngineError::MathOverflow)?;
+        self.pot = self.pot.checked_sub(amount).ok_or(EngineError::MathOverflow)?;
+        Ok(Some(UncalledBet { seat: seat as u8, amount }))
+    }
+
+    fn advance_round(&mut self) {
+        match self.round {
+            Round::PreFlop => {
+                self.deal_board(3);
+                self.round = Round::Flop;
+                self.game_state = GameState::Flop;
+            }
+            Round::Flop => {
+                self.deal_board(1);
+                self.round = Round::Turn;
+                self.game_state = GameState::Turn;
+            }
+            Round::Turn => {
+                self.deal_board(1);
+                self.round = Round::River;
+                self.game_state = GameState::River;
+            }
+            Round::River => {
+                self.game_state = GameState::Showdown;
+            }
+        }
+
+        for player in self.players.iter_mut().flatten() {
+            if player.can_act() {
+                player.has_acted = false;
+            }
+        }
+        self.last_raise_amount = 0;
+        self.min_raise = self.big_blind;
+    }
+
+    /// Every player still in the hand agreed to run it twice, and none of them
+    /// has betting decisions left
+    fn wants_run_twice(&self) -> bool {
+        let live = || self.players.iter().flatten().filter(|p| p.in_hand());
+        live().count() >= 2 && live().filter(|p| p.can_act()).count() <= 1 && live().all(|p| p.run_twice)
+    }
+
+    /// Deal the streets after the first `dealt` community cards a second time
+    /// from where the first runout left the deck, burning before each street
+    fn deal_second_board(&mut self, dealt: usize) {
+        let (board, position) = self.runout(dealt);
+        self.second_board = Some(board);
+        self.deck_position = position as u8;
+    }
+
+    /// The community cards with the streets after the first `dealt` filled in
+    /// from the next undealt cards, burning before each street. Also returns
+    /// the deck position after the runout.
+    fn runout(&self, dealt: usize) -> ([Option<Card>; BOARD_CARDS], usize) {
+        let deck = Deck::shuffled(&self.deck_seed);
+        let mut position = self.deck_position as usize;
+        let mut board = self.community_cards;
+        for (slot, card) in board.iter_mut().enumerate().skip(dealt) {
+            if slot == 0 || slot >= 3 {
+                position += 1;
+            }
+            *card = deck.card(position);
+            position += 1;
+        }
+        (board, position)
+    }
+
+    /// Burn a card, then deal `count` community cards from the hand's deck
+    fn deal_board(&mut self, count: usize) {
+        let deck = Deck::shuffled(&self.deck_seed);
+        let mut position = self.deck_position as usize + 1;
+        let dealt = self.community_cards.iter().filter(|c| c.is_some()).count();
+        for slot in dealt..(dealt + count).min(BOARD_CARDS) {
+            self.community_cards[slot] = deck.card(position);
+            position += 1;
+        }
+        self.deck_position = position as u8;
+    }
+}
@@ -0,0 +1,181 @@
+use poker_engine::*;
+use proptest::prelude::*;
+
+/// Decisions a driver may take before the hand is played out with checks and calls
+const MAX_STEPS: usize = 200;
+
+fn action_type() -> impl Strategy<Value = ActionType> {
+    prop_oneof![
+        Just(ActionType::Fold),
+        Just(ActionType::Check),
+        Just(ActionType::Call),
+        Just(ActionType::Raise),
+        Just(ActionType::AllIn),
+    ]
+}
+
+fn amount() -> impl Strategy<Value = Option<u64>> {
+    prop_oneof![
+        3 => Just(None),
+        6 => (0u64..2_000).prop_map(Some),
+        1 => any::<u64>().prop_map(Some),
+    ]
+}
+
+/// Seat (including out-of-range seats), action and raise amount
+fn step() -> impl Strategy<Value = (u8, ActionType, Option<u64>)> {
+    (0u8..10, action_type(), amount())
+}
+
+prop_compose! {
+    fn started_table()(
+        big_blind in 2u64..100,
+        sb_ratio in 1u64..100,
+        stacks in prop::collection::vec(prop::option::weighted(0.6, 1u64..5_000), MAX_SEATS),
+        deck_seed in any::<[u8; 32]>(),
+    ) -> Table {
+        let small_blind = (big_blind * sb_ratio / 100).clamp(1, big_blind - 1);
+        let mut table = Table::new(small_blind, big_blind, MAX_SEATS as u8);
+        for (seat, stack) in stacks.iter().enumerate() {
+            if let Some(stack) = stack {
+                table.seat_player(seat as u8, [seat as u8 + 1; 32], *stack).unwrap();
+            }
+        }
+        // Tables without two funded players are rejected and left untouched
+        let before = table.clone();
+        if table.start_hand(deck_seed).is_err() {
+            assert_eq!(table, before);
+        }
+        table
+    }
+}
+
+fn chips(table: &Table) -> u64 {
+    table.chips_in_play().expect("chip total overflow")
+}
+
+/// Finish the hand with the most passive legal action
+fn play_out(table: &mut Table) {
+    let mut decisions = 0;
+    while table.game_state.is_betting() {
+        let seat = table.current_player.expect("betting without a current player");
+        let player = table.player(seat).unwrap();
+        let action = if player.bet < table.current_bet() { ActionType::Call } else { ActionType::Check };
+        table.act(seat, action, None).unwrap();
+        decisions += 1;
+        assert!(decisions <= MAX_SEATS * 5, "hand did not terminate");
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(512))]
+
+    #[test]
+    fn arbitrary_action_sequences_keep_invariants(
+        mut table in started_table(),
+        steps in prop::collection::vec(step(), 0..MAX_STEPS),
+    ) {
+        let total = chips(&table);
+        prop_assert!(table.check_invariants().is_ok());
+
+        for (seat, action, amount) in steps {
+            let before = table.clone();
+            match table.act(seat, action, amount) {
+                Ok(outcome) => {
+                    prop_assert_eq!(before.current_player, Some(seat));
+                    let returned = outcome.uncalled.filter(|u| u.seat == seat).map_or(0, |u| u.amount);
+                    prop_assert_eq!(before.player(seat).unwrap().stack - outcome.amount + returned, table.player(seat).unwrap().stack);
+                    // Only a bet nobody can match any more is handed back
+                    if let Some(uncalled) = outcome.uncalled {
+                        prop_assert!(!table.game_state.is_betting());
+                        prop_assert!(uncalled.amount > 0);
+                    }
+                }
+                // Rejected actions must not change anything
+                Err(_) => prop_assert_eq!(&table, &before),
+            }
+            prop_assert_eq!(table.check_invariants(), Ok(()));
+            prop_assert_eq!(chips(&table), total);
+        }
+
+        if table.game_state == GameState::Waiting {
+            return Ok(());
+        }
+        play_out(&mut table);
+        let contested = table.players.iter().flatten().filter(|p| p.in_hand()).count() > 1;
+        if contested {
+            prop_assert_eq!(table.game_state, GameState::Showdown);
+            prop_assert_eq!(table.community_cards.iter().flatten().count(), BOARD_CARDS);
+
+            let showdown = table.showdown().unwrap();
+            prop_assert!(showdown.hands.iter().flatten().count() >= 2);
+        } else {
+            // Folded around: the hand ends without a showdown
+            prop_assert_eq!(table.game_state, GameState::Finished);
+            prop_assert_eq!(table.showdown(), Err(EngineError::InvalidGameState));
+        }
+        prop_assert_eq!(chips(&table), total);
+
+        let settlement = table.settle().unwrap();
+        prop_assert_eq!(settlement.payouts.iter().sum::<u64>(), settlement.pots.total());
+        prop_assert_eq!(table.game_state, GameState::Waiting);
+        prop_assert_eq!(table.pot, 0);
+        prop_assert_eq!(chips(&table), total);
+        prop_assert_eq!(table.check_invariants(), Ok(()));
+    }
+
+    #[test]
+    fn out_of_turn_actions_are_rejected(
+        mut table in started_table(),
+        seat in 0u8..10,
+        action in action_type(),
+        amount in amount(),
+    ) {
+        prop_assume!(table.game_state.is_betting() && table.current_player != Some(seat));
+        let before = table.clone();
+        prop_assert!(table.act(seat, action, amount).is_err());
+        prop_assert_eq!(table, before);
+    }
+
+    #[test]
+    fn seven_card_evaluation_matches_best_five(cards in prop::sample::subsequence((0u8..52).collect::<Vec<_>>(), 7)) {
+        let cards: Vec<Card> = cards.into_iter().map(Card::from_u8).collect();
+        let mut best = None;
+        for skip_a in 0..7 {
+            for skip_b in skip_a + 1..7 {
+                let five: Vec<Card> = (0..7)
+                    .filter(|&i| i != skip_a && i != skip_b)
+                    .map(|i| cards[i])
+                    .collect();
+                best = best.max(Some(evaluate(&five)));
+            }
+        }
+        prop_assert_eq!(Some(evaluate(&cards)), best);
+    }
+
+    #[test]
+    fn pots_split_every_chip(
+        contributions in prop::array::uniform9(0u64..10_000),
+        live in prop::array::uniform9(any::<bool>()),
+        categories in prop::array::uniform9(0u8..3),
+        button in 0u8..9,
+    ) {
+        prop_assume!(live.iter().zip(contributions.iter()).any(|(&l, &c)| l && c > 0));
+        let pots = build_pots(&contributions, &live).unwrap();
+        prop_assert_eq!(pots.total(), contributions.iter().sum::<u64>());
+
+        // Only three distinct strengths so split pots are common
+        let mut hands = [None; MAX_SEATS];
+        for seat in 0..MAX_SEATS {
+            if live[seat] {
+                let pair = Card::new(Suit::Clubs, Rank::from_u8(categories[seat]));
+                hands[seat] = Some(evaluate(&[pair, Card::new(Suit::Hearts, pair.rank)]));
+            }
+        }
+        let payouts = award(&pots, &hands, button).unwrap();
+        prop_assert_eq!(payouts.iter().sum::<u64>(), pots.total());
+        for seat in 0..MAX_SEATS {
+            prop_assert!(payouts[seat] == 0 || live[seat]);
+        }
+    }
+}
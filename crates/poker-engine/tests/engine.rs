@@ -0,0 +1,365 @@
+use poker_engine::*;
+
+fn card(s: &str) -> Card {
+    let bytes = s.as_bytes();
+    let rank = match bytes[0] {
+        b'2'..=b'9' => Rank::from_u8(bytes[0] - b'2'),
+        b'T' => Rank::Ten,
+        b'J' => Rank::Jack,
+        b'Q' => Rank::Queen,
+        b'K' => Rank::King,
+        b'A' => Rank::Ace,
+        _ => panic!("bad rank in {s}"),
+    };
+    let suit = match bytes[1] {
+        b'c' => Suit::Clubs,
+        b'd' => Suit::Diamonds,
+        b'h' => Suit::Hearts,
+        b's' => Suit::Spades,
+        _ => panic!("bad suit in {s}"),
+    };
+    Card::new(suit, rank)
+}
+
+fn hand(cards: &str) -> HandValue {
+    let cards: Vec<Card> = cards.split_whitespace().map(card).collect();
+    evaluate(&cards)
+}
+
+fn id(n: u8) -> [u8; 32] {
+    [n; 32]
+}
+
+fn chips(table: &Table) -> u64 {
+    table.players.iter().flatten().map(|p| p.stack).sum::<u64>() + table.pot
+}
+
+#[test]
+fn evaluates_categories_from_seven_cards() {
+    assert_eq!(hand("As Ks Qs Js Ts 2d 3c").category, HandCategory::StraightFlush);
+    assert_eq!(hand("9c 9d 9h 9s Ad 2c 3c").category, HandCategory::FourOfAKind);
+    assert_eq!(hand("9c 9d 9h 4s 4d 4c 3c").category, HandCategory::FullHouse);
+    assert_eq!(hand("2h 7h 9h Jh Kh Ac Ad").category, HandCategory::Flush);
+    assert_eq!(hand("Ac 2d 3h 4s 5c Kd Qh").category, HandCategory::Straight);
+    assert_eq!(hand("7c 7d 7h As Kc 2d 3h").category, HandCategory::ThreeOfAKind);
+    assert_eq!(hand("7c 7d 5h 5s 3c 3d Ah").category, HandCategory::TwoPair);
+    assert_eq!(hand("7c 7d 5h 2s 3c Jd Ah").category, HandCategory::OnePair);
+    assert_eq!(hand("7c 9d 5h 2s 3c Jd Ah").category, HandCategory::HighCard);
+}
+
+#[test]
+fn compares_hands_by_kickers_and_wheel() {
+    assert!(hand("Ac Ad Kh 7s 3c") > hand("Ac Ad Qh Js Tc"));
+    assert!(hand("2c 3d 4h 5s 6c") > hand("Ac 2d 3h 4s 5c"));
+    // Full house picks the higher of two remaining trips/pairs
+    assert_eq!(hand("9c 9d 9h 4s 4d 4c Kc Kd"), hand("9c 9d 9h Ks Kd"));
+    // Third pair only counts as a kicker
+    assert_eq!(hand("7c 7d 5h 5s 3c 3d 2h"), hand("7c 7d 5h 5s 3c"));
+}
+
+#[test]
+fn side_pots_follow_contribution_levels() {
+    let mut contributions = [0u64; MAX_SEATS];
+    let mut live = [false; MAX_SEATS];
+    contributions[..4].copy_from_slice(&[100, 300, 500, 500]);
+    live[..3].fill(true);
+
+    let pots = build_pots(&contributions, &live).unwrap();
+    let amounts: Vec<u64> = pots.as_slice().iter().map(|p| p.amount).collect();
+    assert_eq!(amounts, vec![400, 600, 400]);
+    assert_eq!(pots.as_slice()[2].eligible, 0b100);
+    assert_eq!(pots.total(), 1400);
+}
+
+#[test]
+fn split_pot_odd_chip_goes_left_of_button() {
+    let mut contributions = [0u64; MAX_SEATS];
+    let mut live = [false; MAX_SEATS];
+    contributions[..3].copy_from_slice(&[101, 101, 101]);
+    live[..3].fill(true);
+    let pots = build_pots(&contributions, &live).unwrap();
+
+    let mut hands = [None; MAX_SEATS];
+    hands[0] = Some(hand("As Ks Qd Jc 9h"));
+    hands[1] = Some(hand("Ad Kd Qh Js 9c"));
+    hands[2] = Some(hand("2c 3d 4h 5s 7c"));
+
+    let payouts = award(&pots, &hands, 0).unwrap();
+    assert_eq!(&payouts[..3], &[151, 152, 0]);
+}
+
+#[test]
+fn plays_a_hand_to_settlement() {
+    let mut table = Table::new(10, 20, 6);
+    table.seat_player(0, id(1), 1000).unwrap();
+    table.seat_player(2, id(2), 1000).unwrap();
+    table.seat_player(4, id(3), 1000).unwrap();
+    table.player_mut(2).unwrap().muck = true;
+    assert_eq!(table.seat_player(2, id(4), 1000), Err(EngineError::SeatOccupied));
+    assert_eq!(table.seat_player(5, id(1), 1000), Err(EngineError::AlreadySeated));
+    assert_eq!(table.seat_player(6, id(4), 1000), Err(EngineError::InvalidSeat));
+
+    table.start_hand([7; 32]).unwrap();
+    assert_eq!(table.dealer_position, 0);
+    assert_eq!(table.small_blind_position, 2);
+    assert_eq!(table.big_blind_position, 4);
+    assert_eq!(table.current_player, Some(0));
+    assert_eq!(table.pot, 30);
+
+    assert_eq!(table.act(2, ActionType::Call, None), Err(EngineError::NotPlayersTurn));
+    table.act(0, ActionType::Call, None).unwrap();
+    table.act(2, ActionType::Call, None).unwrap();
+    let outcome = table.act(4, ActionType::Check, None).unwrap();
+    assert!(outcome.round_complete);
+    assert_eq!(table.game_state, GameState::Flop);
+    assert_eq!(table.community_cards.iter().flatten().count(), 3);
+
+    while table.game_state.is_betting() {
+        let seat = table.current_player.unwrap();
+        table.act(seat, ActionType::Check, None).unwrap();
+        assert_eq!(chips(&table), 3000);
+    }
+    assert_eq!(table.community_cards.iter().flatten().count(), 5);
+
+    assert_eq!(table.rabbit_hunt(), Err(EngineError::InvalidGameState));
+    let showdown = table.showdown().unwrap();
+    assert_eq!(showdown.hands.iter().flatten().count(), 3);
+    // Nobody bet the river, so the first seat left of the button shows first,
+    // and has to even though they muck
+    assert_eq!(showdown.order[..3], [Some(2), Some(4), Some(0)]);
+    assert!(showdown.shown[2]);
+    // A hand that reached the river leaves nothing to hunt
+    assert_eq!(table.rabbit_hunt().unwrap(), table.community_cards);
+    let payouts = table.payouts().unwrap();
+    let settlement = table.settle().unwrap();
+    assert_eq!(settlement, payouts);
+    assert_eq!(settlement.payouts.iter().sum::<u64>(), 60);
+    assert_eq!(chips(&table), 3000);
+    assert_eq!(table.game_state, GameState::Waiting);
+
+    // The muck preference outlives the hand
+    assert!(table.player(2).unwrap().muck);
+
+    // The button moves clockwise for the next hand
+    table.start_hand([8; 32]).unwrap();
+    assert_eq!(table.dealer_position, 2);
+}
+
+#[test]
+fn force_fold_out_of_turn_keeps_the_action() {
+    let mut table = Table::new(10, 20, 6);
+    table.seat_player(0, id(1), 1000).unwrap();
+    table.seat_player(2, id(2), 1000).unwrap();
+    table.seat_player(4, id(3), 1000).unwrap();
+    table.start_hand([7; 32]).unwrap();
+    assert_eq!(table.current_player, Some(0));
+
+    // The big blind leaves before anyone acts
+    let outcome = table.force_fold(4).unwrap();
+    assert!(!outcome.round_complete);
+    assert!(table.player(4).unwrap().has_folded);
+    assert_eq!(table.current_player, Some(0));
+
+    table.act(0, ActionType::Call, None).unwrap();
+    // The small blind leaves on their turn, which leaves nobody to bet against
+    let outcome = table.force_fold(2).unwrap();
+    assert!(outcome.round_complete);
+    assert_eq!(table.game_state, GameState::Finished);
+    assert_eq!(chips(&table), 3000);
+    assert_eq!(table.force_fold(0), Err(EngineError::InvalidGameState));
+}
+
+#[test]
+fn blinds_both_leaving_leaves_the_pot_to_a_player_who_put_in_nothing() {
+    let mut table = Table::new(10, 20, 6);
+    table.seat_player(0, id(1), 1000).unwrap();
+    table.seat_player(2, id(2), 1000).unwrap();
+    table.seat_player(4, id(3), 1000).unwrap();
+    table.start_hand([7; 32]).unwrap();
+    assert_eq!(table.current_player, Some(0));
+
+    table.force_fold(2).unwrap();
+    table.force_fold(4).unwrap();
+    assert_eq!(table.game_state, GameState::Finished);
+    assert_eq!(table.player(0).unwrap().bet, 0);
+
+    let settlement = table.settle().unwrap();
+    assert_eq!(settlement.payouts[0], 30);
+    assert_eq!(table.player(0).unwrap().stack, 1030);
+    assert_eq!(chips(&table), 3000);
+}
+
+#[test]
+fn folding_around_ends_the_hand_uncontested() {
+    let mut table = Table::new(10, 20, 6);
+    table.seat_player(0, id(1), 1000).unwrap();
+    table.seat_player(2, id(2), 1000).unwrap();
+    table.seat_player(4, id(3), 1000).unwrap();
+    table.start_hand([7; 32]).unwrap();
+
+    table.act(0, ActionType::Raise, Some(40)).unwrap();
+    table.act(2, ActionType::Fold, None).unwrap();
+    let outcome = table.act(4, ActionType::Fold, None).unwrap();
+    assert!(outcome.round_complete);
+    // Only the big blind's 20 of the raise was ever matched
+    assert_eq!(outcome.uncalled, Some(UncalledBet { seat: 0, amount: 40 }));
+    assert_eq!(table.player(0).unwrap().stack, 980);
+    assert_eq!(table.pot, 50);
+
+    // No board is dealt and nobody shows
+    assert_eq!(table.game_state, GameState::Finished);
+    assert_eq!(table.current_player, None);
+    assert_eq!(table.community_cards, [None; BOARD_CARDS]);
+    assert_eq!(table.showdown(), Err(EngineError::InvalidGameState));
+    assert_eq!(table.rabbit_hunt().unwrap().iter().flatten().count(), BOARD_CARDS);
+
+    // The raiser takes the blinds and the called part of the raise
+    let settlement = table.settle().unwrap();
+    assert_eq!(settlement.payouts[0], 50);
+    assert_eq!(table.player(0).unwrap().stack, 1030);
+    assert_eq!(chips(&table), 3000);
+}
+
+#[test]
+fn all_in_runs_out_the_board() {
+    let mut table = Table::new(10, 20, 2);
+    table.seat_player(0, id(1), 500).unwrap();
+    table.seat_player(1, id(2), 300).unwrap();
+    table.start_hand([3; 32]).unwrap();
+
+    // Heads-up the button posts the small blind and acts first preflop
+    assert_eq!(table.small_blind_position, 0);
+    table.act(0, ActionType::AllIn, None).unwrap();
+    let outcome = table.act(1, ActionType::Call, None).unwrap();
+    assert!(outcome.round_complete);
+    assert_eq!(table.game_state, GameState::Showdown);
+    assert_eq!(table.current_player, None);
+
+    assert_eq!(table.second_board, None);
+
+    // The 200 nobody could call goes back to seat 0 before showdown
+    assert_eq!(outcome.uncalled, Some(UncalledBet { seat: 0, amount: 200 }));
+    assert_eq!(table.player(0).unwrap().stack, 200);
+    assert_eq!(table.pot, 600);
+
+    table.showdown().unwrap();
+    let settlement = table.settle().unwrap();
+    assert_eq!(settlement.payouts.iter().sum::<u64>(), 600);
+    assert_eq!(chips(&table), 800);
+}
+
+#[test]
+fn agreed_all_in_runs_the_board_twice() {
+    let mut table = Table::new(10, 20, 2);
+    table.seat_player(0, id(1), 500).unwrap();
+    table.seat_player(1, id(2), 500).unwrap();
+    table.start_hand([5; 32]).unwrap();
+    table.act(0, ActionType::Call, None).unwrap();
+    table.act(1, ActionType::Check, None).unwrap();
+    assert_eq!(table.game_state, GameState::Flop);
+
+    // Both agree before the money goes in on the flop
+    table.vote_run_twice(0, true).unwrap();
+    table.vote_run_twice(1, true).unwrap();
+    let seat = table.current_player.unwrap();
+    table.act(seat, ActionType::AllIn, None).unwrap();
+    table.act(1 - seat, ActionType::Call, None).unwrap();
+    assert_eq!(table.game_state, GameState::Showdown);
+
+    // The flop is shared, the turn and river are dealt again from the same deck
+    let first = table.community_cards;
+    let second = table.second_board.unwrap();
+    assert_eq!(first[..3], second[..3]);
+    assert!(second[3..].iter().all(|c| c.is_some() && !first.contains(c)));
+
+    let showdown = table.showdown().unwrap();
+    assert!(showdown.second_hands.is_some());
+    let settlement = table.settle().unwrap();
+    assert_eq!(settlement.payouts.iter().sum::<u64>(), 1000);
+    assert_eq!(chips(&table), 1000);
+    assert_eq!(table.second_board, None);
+}
+
+#[test]
+fn halved_pots_keep_every_chip() {
+    // A folded blind leaves the pot odd
+    let pots = build_pots(&[100, 100, 1, 0, 0, 0, 0, 0, 0], &[true, true, false, false, false, false, false, false, false]).unwrap();
+    let (first, second) = pots.halve();
+    assert_eq!(first.as_slice()[0].amount, 101);
+    assert_eq!(second.as_slice()[0].amount, 100);
+    assert_eq!(first.total() + second.total(), pots.total());
+}
+
+#[test]
+fn last_aggressor_shows_first_and_beaten_hands_may_muck() {
+    let mut table = Table::new(10, 20, 6);
+    for seat in [0, 2, 4] {
+        table.seat_player(seat, id(seat + 1), 1000).unwrap();
+    }
+    table.start_hand([7; 32]).unwrap();
+    while table.game_state.is_betting() {
+        let seat = table.current_player.unwrap();
+        let action = if table.player(seat).unwrap().bet < table.current_bet() { ActionType::Call } else { ActionType::Check };
+        table.act(seat, action, None).unwrap();
+    }
+
+    // Seat 4 bet the river and holds the middle hand; seat 0 has the best
+    let board = ["2c", "7d", "9h", "Js", "Kc"];
+    table.community_cards = board.map(|c| Some(card(c)));
+    for (seat, cards) in [(0, ["Ah", "Ad"]), (2, ["3s", "4s"]), (4, ["Kd", "Qh"])] {
+        table.player_mut(seat).unwrap().hole_cards = cards.map(|c| Some(card(c)));
+    }
+    table.last_aggressor = Some(4);
+    table.player_mut(2).unwrap().muck = true;
+    assert_eq!(table.show_or_muck(2, true), Ok(()));
+
+    let mut mucked = table.clone();
+    mucked.player_mut(2).unwrap().show = None;
+    let showdown = mucked.showdown().unwrap();
+    assert_eq!(showdown.order[..3], [Some(4), Some(0), Some(2)]);
+    assert_eq!([showdown.shown[4], showdown.shown[0], showdown.shown[2]], [true, true, false]);
+
+    // This hand's decision overrides the standing preference
+    let showdown = table.showdown().unwrap();
+    assert!(showdown.shown[2]);
+    assert_eq!(table.show_or_muck(2, false), Err(EngineError::InvalidGameState));
+}
+
+#[test]
+fn beaten_quad_eights_using_both_hole_cards_is_a_bad_beat() {
+    let mut table = Table::new(10, 20, 6);
+    for seat in [0, 2, 4] {
+        table.seat_player(seat, id(seat + 1), 1000).unwrap();
+    }
+    table.start_hand([7; 32]).unwrap();
+    while table.game_state.is_betting() {
+        let seat = table.current_player.unwrap();
+        let action = if table.player(seat).unwrap().bet < table.current_bet() { ActionType::Call } else { ActionType::Check };
+        table.act(seat, action, None).unwrap();
+    }
+    assert_eq!(table.bad_beat(), None);
+
+    // Seat 2's pocket eights make quads and lose to seat 0's straight flush
+    let board = ["8c", "8d", "9h", "Th", "Jh"];
+    table.community_cards = board.map(|c| Some(card(c)));
+    for (seat, cards) in [(0, ["Qh", "Kh"]), (2, ["8h", "8s"]), (4, ["2c", "3c"])] {
+        table.player_mut(seat).unwrap().hole_cards = cards.map(|c| Some(card(c)));
+    }
+    let mut one_card = table.clone();
+    table.showdown().unwrap();
+    assert_eq!(table.bad_beat(), Some(BadBeat { loser: 2, winner: 0 }));
+
+    // Quads with three eights on the board only play one hole card
+    one_card.community_cards = ["8c", "8d", "8h", "9h", "Th"].map(|c| Some(card(c)));
+    one_card.player_mut(0).unwrap().hole_cards = [Some(card("Jh")), Some(card("Qh"))];
+    one_card.player_mut(2).unwrap().hole_cards = [Some(card("8s")), Some(card("2d"))];
+    one_card.showdown().unwrap();
+    assert!(one_card.hand_values()[2] < one_card.hand_values()[0]);
+    assert_eq!(one_card.bad_beat(), None);
+
+    // The drop comes out of the pot before it is awarded
+    let settlement = table.settle_with_drop(500).unwrap();
+    assert_eq!(settlement.drop, 3);
+    assert_eq!(settlement.payouts[0], 57);
+    assert_eq!(chips(&table), 2997);
+}
@@ -0,0 +1,153 @@
+//! Deterministic hand simulator for the poker engine.
+//!
+//! Plays randomly generated tables with scripted or random players and checks
+//! table invariants after every transition. Each table is driven by a single
+//! 64-bit seed, printed with any violation so the hand can be replayed with
+//! `--replay <seed>`.
+use std::process::ExitCode;
+use std::time::Instant;
+
+mod rng;
+mod sim;
+mod strategy;
+
+use rng::Rng;
+use sim::{run_case, Config, Stats, Violation};
+use strategy::Strategy;
+
+const USAGE: &str = "\
+Usage: poker-sim [OPTIONS]
+
+Options:
+  --hands <N>             Total hands to simulate [default: 100000]
+  --hands-per-table <N>   Hands played at each generated table [default: 20]
+  --seed <SEED>           Master seed for generating tables [default: 0]
+  --strategy <NAME>       random | calling-station | aggressive | passive | mixed [default: mixed]
+  --replay <SEED>         Replay a single table seed with a full trace
+  --max-violations <N>    Stop after this many violations [default: 10]
+  -h, --help              Print this help";
+
+struct Args {
+    hands: u64,
+    hands_per_table: u64,
+    seed: u64,
+    strategy: Option<Strategy>,
+    replay: Option<u64>,
+    max_violations: usize,
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(err) if err.is_empty() => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Err(err) => {
+            eprintln!("{err}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let config = Config {
+        hands_per_table: args.hands_per_table,
+        strategy: args.strategy,
+        trace: args.replay.is_some(),
+    };
+
+    if let Some(seed) = args.replay {
+        return match run_case(seed, &config) {
+            Ok(stats) => {
+                println!("seed {seed:#018x}: {} hands, no violations", stats.hands);
+                ExitCode::SUCCESS
+            }
+            Err(violation) => {
+                report(&violation);
+                ExitCode::FAILURE
+            }
+        };
+    }
+
+    let started = Instant::now();
+    let mut master = Rng::new(args.seed);
+    let mut stats = Stats::default();
+    let mut violations = Vec::new();
+
+    while stats.hands < args.hands && violations.len() < args.max_violations {
+        let seed = master.next_u64();
+        match run_case(seed, &config) {
+            Ok(case) => stats.merge(&case),
+            Err(violation) => {
+                report(&violation);
+                stats.merge(&violation.stats);
+                violations.push(violation);
+            }
+        }
+    }
+
+    let elapsed = started.elapsed().as_secs_f64();
+    println!(
+        "{} tables, {} hands, {} actions, {} showdowns in {elapsed:.1}s ({:.0} hands/s)",
+        stats.cases,
+        stats.hands,
+        stats.actions,
+        stats.showdowns,
+        stats.hands as f64 / elapsed.max(f64::EPSILON)
+    );
+
+    if violations.is_empty() {
+        println!("no invariant violations");
+        ExitCode::SUCCESS
+    } else {
+        println!("{} invariant violations", violations.len());
+        ExitCode::FAILURE
+    }
+}
+
+fn report(violation: &Violation) {
+    eprintln!(
+        "violation at seed {:#018x}, hand {}: {}\n  replay with: poker-sim --replay {:#018x}",
+        violation.seed, violation.hand, violation.message, violation.seed
+    );
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut parsed = Args {
+        hands: 100_000,
+        hands_per_table: 20,
+        seed: 0,
+        strategy: None,
+        replay: None,
+        max_violations: 10,
+    };
+
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| args.next().ok_or(format!("missing value for {name}"));
+        match arg.as_str() {
+            "--hands" => parsed.hands = parse_number(&value(&arg)?)?,
+            "--hands-per-table" => parsed.hands_per_table = parse_number(&value(&arg)?)?.max(1),
+            "--seed" => parsed.seed = parse_number(&value(&arg)?)?,
+            "--replay" => parsed.replay = Some(parse_number(&value(&arg)?)?),
+            "--max-violations" => parsed.max_violations = parse_number(&value(&arg)?)?.max(1) as usize,
+            "--strategy" => {
+                let name = value(&arg)?;
+                parsed.strategy = match name.as_str() {
+                    "mixed" => None,
+                    other => Some(Strategy::parse(other).ok_or(format!("unknown strategy {other}"))?),
+                };
+            }
+            "-h" | "--help" => return Err(String::new()),
+            other => return Err(format!("unknown argument {other}")),
+        }
+    }
+    Ok(parsed)
+}
+
+/// Decimal or `0x`-prefixed hexadecimal
+fn parse_number(value: &str) -> Result<u64, String> {
+    let parsed = match value.strip_prefix("0x") {
+        Some(hex) => u64::from_str_radix(hex, 16),
+        None => value.parse(),
+    };
+    parsed.map_err(|_| format!("invalid number {value}"))
+}
@@ -0,0 +1,243 @@
+use poker_engine::{GameState, Table, MAX_SEATS};
+
+use crate::rng::Rng;
+use crate::strategy::Strategy;
+
+/// Upper bound on decisions in one hand before it counts as non-terminating
+pub const MAX_ACTIONS_PER_HAND: u64 = 10_000;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Config {
+    pub hands_per_table: u64,
+    /// Strategy for every seat, or a random one per seat when `None`
+    pub strategy: Option<Strategy>,
+    /// Print every transition (used when replaying a seed)
+    pub trace: bool,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Stats {
+    pub cases: u64,
+    pub hands: u64,
+    pub actions: u64,
+    pub showdowns: u64,
+}
+
+impl Stats {
+    pub fn merge(&mut self, other: &Stats) {
+        self.cases += other.cases;
+        self.hands += other.hands;
+        self.actions += other.actions;
+        self.showdowns += other.showdowns;
+    }
+}
+
+/// An invariant broken while simulating the case started from `seed`
+#[derive(Clone, Debug)]
+pub struct Violation {
+    pub seed: u64,
+    pub hand: u64,
+    pub message: String,
+    /// What the case got through before the violation
+    pub stats: Stats,
+}
+
+/// Simulate one table, seeded entirely by `seed`, for up to `hands_per_table` hands
+pub fn run_case(seed: u64, config: &Config) -> Result<Stats, Violation> {
+    let mut case = Case::new(seed, config);
+    case.run().map_err(|message| Violation { seed, hand: case.stats.hands, message, stats: case.stats })?;
+    Ok(case.stats)
+}
+
+struct Case<'a> {
+    config: &'a Config,
+    rng: Rng,
+    table: Table,
+    strategies: [Strategy; MAX_SEATS],
+    /// Chips that should exist at the table (stacks plus pot)
+    total_chips: u64,
+    next_id: u64,
+    stats: Stats,
+}
+
+impl<'a> Case<'a> {
+    fn new(seed: u64, config: &'a Config) -> Self {
+        let mut rng = Rng::new(seed);
+        let big_blind = rng.range(2, 200);
+        let small_blind = rng.range(1, big_blind - 1);
+        let max_players = rng.range(2, MAX_SEATS as u64) as u8;
+
+        Self {
+            config,
+            rng,
+            table: Table::new(small_blind, big_blind, max_players),
+            strategies: [Strategy::Random; MAX_SEATS],
+            total_chips: 0,
+            next_id: 0,
+            stats: Stats { cases: 1, ..Stats::default() },
+        }
+    }
+
+    fn run(&mut self) -> Result<(), String> {
+        self.trace(format_args!(
+            "table blinds {}/{} max_players {}",
+            self.table.small_blind, self.table.big_blind, self.table.max_players
+        ));
+        let seated = self.rng.range(2, self.table.max_players as u64);
+        for _ in 0..seated {
+            self.seat_random_player()?;
+        }
+
+        while self.stats.hands < self.config.hands_per_table {
+            let funded = self.table.players.iter().flatten().filter(|p| p.stack > 0).count();
+            if funded < 2 {
+                break;
+            }
+            self.play_hand()?;
+            self.reseat()?;
+        }
+        Ok(())
+    }
+
+    fn play_hand(&mut self) -> Result<(), String> {
+        let deck_seed = self.rng.seed_bytes();
+        self.table
+            .start_hand(deck_seed)
+            .map_err(|e| format!("start_hand rejected: {e}"))?;
+        self.stats.hands += 1;
+        self.trace(format_args!(
+            "hand {} dealer {} sb {} bb {}",
+            self.table.hand_number,
+            self.table.dealer_position,
+            self.table.small_blind_position,
+            self.table.big_blind_position
+        ));
+        self.check("start_hand")?;
+
+        let mut actions = 0u64;
+        while self.table.game_state.is_betting() {
+            if actions >= MAX_ACTIONS_PER_HAND {
+                return Err(format!("hand did not terminate after {actions} actions"));
+            }
+            let seat = self
+                .table
+                .current_player
+                .ok_or("betting round without a current player")?;
+            let (action, amount) =
+                self.strategies[seat as usize].choose(&self.table, seat, &mut self.rng);
+            let outcome = self
+                .table
+                .act(seat, action, amount)
+                .map_err(|e| format!("legal {action:?} {amount:?} by seat {seat} rejected: {e}"))?;
+            actions += 1;
+            self.trace(format_args!(
+                "  seat {seat} {action:?} {amount:?} -> {} chips, pot {}, state {:?}",
+                outcome.amount, self.table.pot, self.table.game_state
+            ));
+            self.check("act")?;
+        }
+        self.stats.actions += actions;
+
+        // Everyone else folding ends the hand uncontested, without a showdown
+        match self.table.game_state {
+            GameState::Showdown => {
+                let showdown = self.table.showdown().map_err(|e| format!("showdown rejected: {e}"))?;
+                self.stats.showdowns += 1;
+                for (seat, hand) in showdown.hands.iter().enumerate() {
+                    if let Some(hand) = hand {
+                        self.trace(format_args!("  seat {seat} shows {:?} {:?}", hand.category, hand.ranks));
+                    }
+                }
+                self.check("showdown")?;
+            }
+            GameState::Finished => {
+                let live = self.table.players.iter().flatten().filter(|p| p.in_hand()).count();
+                if live != 1 {
+                    return Err(format!("hand finished without a showdown with {live} players left"));
+                }
+                self.trace(format_args!("  uncontested"));
+            }
+            state => return Err(format!("betting ended in {state:?} instead of Showdown or Finished")),
+        }
+
+        let settlement = self.table.settle().map_err(|e| format!("settle rejected: {e}"))?;
+        if settlement.payouts.iter().sum::<u64>() != settlement.pots.total() {
+            return Err("payouts do not add up to the pots".into());
+        }
+        self.trace(format_args!("  payouts {:?}", settlement.payouts));
+        self.check("settle")
+    }
+
+    /// Busted players may leave and new players may sit down between hands
+    fn reseat(&mut self) -> Result<(), String> {
+        for seat in 0..MAX_SEATS as u8 {
+            let busted = self.table.player(seat).is_some_and(|p| p.stack == 0);
+            if busted && self.rng.chance(50) {
+                let player = self
+                    .table
+                    .unseat_player(seat)
+                    .map_err(|e| format!("unseat_player rejected: {e}"))?;
+                self.total_chips -= player.stack;
+                self.trace(format_args!("seat {seat} leaves"));
+            }
+        }
+        if self.table.player_count < self.table.max_players && self.rng.chance(20) {
+            self.seat_random_player()?;
+        }
+        self.check("reseat")
+    }
+
+    fn seat_random_player(&mut self) -> Result<(), String> {
+        let free: Vec<u8> = (0..MAX_SEATS as u8)
+            .filter(|&s| self.table.player(s).is_none())
+            .collect();
+        let seat = free[self.rng.range(0, free.len() as u64 - 1) as usize];
+        let stack = self.table.big_blind * self.rng.range(1, 300);
+        self.next_id += 1;
+        let mut id = [0u8; 32];
+        id[..8].copy_from_slice(&self.next_id.to_le_bytes());
+
+        self.table
+            .seat_player(seat, id, stack)
+            .map_err(|e| format!("seat_player rejected: {e}"))?;
+        self.total_chips += stack;
+        self.strategies[seat as usize] = match self.config.strategy {
+            Some(strategy) => strategy,
+            None => Strategy::ALL[self.rng.range(0, Strategy::ALL.len() as u64 - 1) as usize],
+        };
+        self.trace(format_args!(
+            "seat {seat} joins with {stack} as {:?}",
+            self.strategies[seat as usize]
+        ));
+        Ok(())
+    }
+
+    /// Table invariants that must hold after every transition
+    fn check(&self, step: &str) -> Result<(), String> {
+        let table = &self.table;
+        table
+            .check_invariants()
+            .map_err(|e| format!("after {step}: {e}"))?;
+
+        // Stacks are unsigned, so an underflow would show up as a huge stack
+        if let Some(player) = table.players.iter().flatten().find(|p| p.stack > self.total_chips) {
+            return Err(format!("after {step}: stack {} exceeds chips in play", player.stack));
+        }
+        let chips = table
+            .chips_in_play()
+            .ok_or_else(|| format!("after {step}: chip total overflow"))?;
+        if chips != self.total_chips {
+            return Err(format!(
+                "after {step}: chips not conserved, {} expected but {chips} at the table",
+                self.total_chips
+            ));
+        }
+        Ok(())
+    }
+
+    fn trace(&self, args: std::fmt::Arguments) {
+        if self.config.trace {
+            println!("{args}");
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use poker_engine::{ActionType, Table};
+
+use crate::rng::Rng;
+
+/// How a simulated player picks its actions
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Strategy {
+    /// Uniformly random legal action and raise size
+    Random,
+    /// Checks when possible, otherwise calls
+    CallingStation,
+    /// Min-raises whenever allowed and shoves now and then
+    Aggressive,
+    /// Folds to any bet, checks otherwise
+    Passive,
+}
+
+impl Strategy {
+    pub const ALL: [Strategy; 4] = [
+        Strategy::Random,
+        Strategy::CallingStation,
+        Strategy::Aggressive,
+        Strategy::Passive,
+    ];
+
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "random" => Some(Strategy::Random),
+            "calling-station" => Some(Strategy::CallingStation),
+            "aggressive" => Some(Strategy::Aggressive),
+            "passive" => Some(Strategy::Passive),
+            _ => None,
+        }
+    }
+
+    /// Pick a legal action for the player at `seat`
+    pub fn choose(&self, table: &Table, seat: u8, rng: &mut Rng) -> (ActionType, Option<u64>) {
+        let legal = LegalActions::for_seat(table, seat);
+        match self {
+            Strategy::Random => {
+                let mut options = Vec::with_capacity(5);
+                options.push(ActionType::Fold);
+                if legal.to_call == 0 {
+                    options.push(ActionType::Check);
+                } else {
+                    options.push(ActionType::Call);
+                }
+                if legal.can_raise() {
+                    options.push(ActionType::Raise);
+                }
+                options.push(ActionType::AllIn);
+                let action = options[rng.range(0, options.len() as u64 - 1) as usize];
+                let amount = (action == ActionType::Raise)
+                    .then(|| rng.range(legal.min_raise, legal.max_raise()));
+                (action, amount)
+            }
+            Strategy::CallingStation => (legal.check_or_call(), None),
+            Strategy::Aggressive => {
+                if rng.chance(10) {
+                    (ActionType::AllIn, None)
+                } else if legal.can_raise() {
+                    (ActionType::Raise, Some(legal.min_raise))
+                } else {
+                    (legal.check_or_call(), None)
+                }
+            }
+            Strategy::Passive => {
+                if legal.to_call == 0 {
+                    (ActionType::Check, None)
+                } else {
+                    (ActionType::Fold, None)
+                }
+            }
+        }
+    }
+}
+
+/// Betting limits for the player whose turn it is
+#[derive(Clone, Copy, Debug)]
+pub struct LegalActions {
+    pub to_call: u64,
+    pub stack: u64,
+    pub min_raise: u64,
+}
+
+impl LegalActions {
+    pub fn for_seat(table: &Table, seat: u8) -> Self {
+        let player = table.player(seat).copied();
+        let bet = player.map_or(0, |p| p.bet);
+        Self {
+            to_call: table.current_bet().saturating_sub(bet),
+            stack: player.map_or(0, |p| p.stack),
+            min_raise: table.min_raise,
+        }
+    }
+
+    pub fn can_raise(&self) -> bool {
+        self.to_call.saturating_add(self.min_raise) <= self.stack
+    }
+
+    pub fn max_raise(&self) -> u64 {
+        self.stack.saturating_sub(self.to_call)
+    }
+
+    pub fn check_or_call(&self) -> ActionType {
+        if self.to_call == 0 {
+            ActionType::Check
+        } else {
+            ActionType::Call
+        }
+    }
+}
@@ -2,7 +2,7 @@ import { useState } from 'react';
 import { useWallet, useConnection } from '@solana/wallet-adapter-react';
 import { PublicKey, SystemProgram } from '@solana/web3.js';
 import * as anchor from '@coral-xyz/anchor';
-import { getProgram } from '../utils/anchor';
+import { getProgram, getTablePDA } from '../utils/anchor';
 import { Table, Player, ActionType } from '../types';
 import toast from 'react-hot-toast';
 import { motion } from 'framer-motion';
@@ -39,14 +39,18 @@ export default function ActionButtons({ table, player, onActionComplete }: Actio
       setActing(true);
       const program = getProgram(connection, { publicKey, signTransaction, sendTransaction } as any);
       
-      // Get table PDA (simplified)
-      const tablePDA = new PublicKey(table.creator);
+      const [tablePDA] = getTablePDA(new PublicKey(table.creator), table.tableId);
 
       const tx = await program.methods
         .action(actionType, amount ? new anchor.BN(amount) : null)
         .accounts({
           table: tablePDA,
+          signer: publicKey,
           player: publicKey,
+          session: null,
+          relayer: null,
+          sponsor: null,
+          instructions: null,
         })
         .rpc();
 
@@ -3,7 +3,7 @@ import { useWallet, useConnection } from '@solana/wallet-adapter-react';
 import { PublicKey, SystemProgram } from '@solana/web3.js';
 import * as anchor from '@coral-xyz/anchor';
 import { useGameStore } from '../store/gameStore';
-import { getProgram, getTablePDA } from '../utils/anchor';
+import { getProgram, getProfilePDA, getTablePDA } from '../utils/anchor';
 import toast from 'react-hot-toast';
 
 export default function Lobby() {
@@ -49,7 +49,9 @@ export default function Lobby() {
           new anchor.BN(smallBlind),
           new anchor.BN(bigBlind),
           maxPlayers,
-          new anchor.BN(buyIn)
+          new anchor.BN(buyIn),
+          { public: {} },
+          null
         )
         .accounts({
           table: tablePDA,
@@ -85,10 +87,15 @@ export default function Lobby() {
       const [tablePDA] = getTablePDA(publicKey, tableIdNum);
 
       const tx = await program.methods
-        .joinTable(seat)
+        .joinTable(seat, null)
         .accounts({
           table: tablePDA,
           player: publicKey,
+          profile: getProfilePDA(publicKey)[0],
+          allowlist: null,
+          gateTokenAccount: null,
+          gateMetadata: null,
+          waitlist: null,
           systemProgram: SystemProgram.programId,
         })
         .rpc();
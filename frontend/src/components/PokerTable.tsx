@@ -2,7 +2,7 @@ import { useState, useEffect, useCallback } from 'react';
 import { useWallet, useConnection } from '@solana/wallet-adapter-react';
 import { PublicKey } from '@solana/web3.js';
 import { useGameStore } from '../store/gameStore';
-import { getProgram } from '../utils/anchor';
+import { getProgram, getTablePDA } from '../utils/anchor';
 import { subscribeToTableAccount, subscribeToLogs } from '../utils/websocket';
 import Card from './Card';
 import ActionButtons from './ActionButtons';
@@ -22,7 +22,7 @@ export default function PokerTable() {
     if (!currentTable || !connection) return;
 
     // Subscribe to table account changes
-    const tablePDA = new PublicKey(currentTable.creator); // Simplified - should be actual PDA
+    const [tablePDA] = getTablePDA(new PublicKey(currentTable.creator), currentTable.tableId);
     const unsubscribeAccount = subscribeToTableAccount(
       connection,
       tablePDA,
@@ -73,14 +73,16 @@ export default function PokerTable() {
       setLoading(true);
       const program = getProgram(connection, { publicKey, signTransaction, sendTransaction } as any);
       
-      // Get table PDA (simplified)
-      const tablePDA = new PublicKey(currentTable!.creator);
+      const [tablePDA] = getTablePDA(new PublicKey(currentTable!.creator), currentTable!.tableId);
 
       const tx = await program.methods
         .startGame()
         .accounts({
           table: tablePDA,
           authority: publicKey,
+          relayer: null,
+          sponsor: null,
+          instructions: null,
         })
         .rpc();
 
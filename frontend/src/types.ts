@@ -114,6 +114,7 @@ export interface ActionTakenEvent {
   amount: number;
   newPot: number;
   round: Round;
+  timeBank: number; // seconds the player has left
 }
 
 export interface RoundEndedEvent {
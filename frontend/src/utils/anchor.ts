@@ -1,4 +1,4 @@
-import { Program, AnchorProvider, Idl } from '@coral-xyz/anchor';
+import { Program, AnchorProvider, BN, Idl } from '@coral-xyz/anchor';
 import { Connection, PublicKey } from '@solana/web3.js';
 import { Wallet } from '@solana/wallet-adapter-base';
 import idl from '../idl/multiplayer_poker.json';
@@ -11,7 +11,7 @@ export function getProgram(connection: Connection, wallet: Wallet): Program {
     wallet as any,
     { commitment: 'confirmed' }
   );
-  return new Program(idl as Idl, PROGRAM_ID, provider);
+  return new Program(idl as Idl, provider);
 }
 
 export function getTablePDA(creator: PublicKey, tableId: number): [PublicKey, number] {
@@ -19,8 +19,15 @@ export function getTablePDA(creator: PublicKey, tableId: number): [PublicKey, nu
     [
       Buffer.from('table'),
       creator.toBuffer(),
-      Buffer.from(tableId.toString()),
+      new BN(tableId).toArrayLike(Buffer, 'le', 8),
     ],
     PROGRAM_ID
   );
 }
+
+export function getProfilePDA(player: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('profile'), player.toBuffer()],
+    PROGRAM_ID
+  );
+}
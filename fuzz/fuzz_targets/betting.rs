@@ -0,0 +1,117 @@
+//! Arbitrary `ActionType` sequences against the `Table` account.
+//!
+//! Every step goes through the same load/act/store path as `action::handler`,
+//! then the hand is checked down and run through showdown (unless folded
+//! around) and distribution.
+//! Chips must be conserved, `current_player` must always point at a seat that
+//! can act, and nothing may panic.
+#![no_main]
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use multiplayer_poker::state::*;
+
+#[derive(Arbitrary, Debug)]
+struct Input {
+    small_blind: u16,
+    big_blind: u16,
+    stacks: [Option<u32>; 9],
+    deck_seed: [u8; 32],
+    steps: Vec<Step>,
+}
+
+#[derive(Arbitrary, Debug)]
+struct Step {
+    seat: u8,
+    action: u8,
+    amount: Option<u64>,
+}
+
+fn action_type(value: u8) -> ActionType {
+    match value % 5 {
+        0 => ActionType::Fold,
+        1 => ActionType::Check,
+        2 => ActionType::Call,
+        3 => ActionType::Raise,
+        _ => ActionType::AllIn,
+    }
+}
+
+fn chips(table: &Table) -> u64 {
+    table.players().iter().flatten().map(|p| p.stack).sum::<u64>() + table.pot
+}
+
+fn check(table: &Table, total: u64) {
+    let game = table.to_engine();
+    assert_eq!(game.check_invariants(), Ok(()));
+    assert_eq!(chips(table), total);
+    if let Some(seat) = table.current_player() {
+        let player = table.get_player(seat).expect("current player seat is empty");
+        assert!(!player.has_folded && !player.is_all_in && player.hole_cards[0].is_some());
+    }
+}
+
+fuzz_target!(|input: Input| {
+    let big_blind = u64::from(input.big_blind.max(2));
+    let small_blind = u64::from(input.small_blind).clamp(1, big_blind - 1);
+    let mut table = Table {
+        small_blind,
+        big_blind,
+        max_players: 9,
+        buy_in: big_blind * 10,
+        min_raise: big_blind,
+        action_timeout: 60,
+        ..Table::default()
+    };
+
+    let mut game = table.to_engine();
+    for (seat, stack) in input.stacks.iter().enumerate() {
+        if let Some(stack) = stack {
+            game.seat_player(seat as u8, [seat as u8 + 1; 32], u64::from(*stack)).unwrap();
+        }
+    }
+    if game.start_hand(input.deck_seed).is_err() {
+        return;
+    }
+    table.apply_engine(&game);
+    let total = chips(&table);
+    check(&table, total);
+
+    for step in &input.steps {
+        let before = table.to_engine();
+        let mut game = table.to_engine();
+        match game.act(step.seat, action_type(step.action).into(), step.amount) {
+            Ok(_) => table.apply_engine(&game),
+            Err(_) => assert_eq!(game, before, "rejected action changed the table"),
+        }
+        check(&table, total);
+    }
+
+    let mut decisions = 0;
+    while !matches!(table.game_state(), GameState::Showdown | GameState::Finished) {
+        let seat = table.current_player().expect("betting without a current player");
+        let mut game = table.to_engine();
+        let to_call = game.current_bet() - game.player(seat).unwrap().bet;
+        let action = if to_call > 0 { ActionType::Call } else { ActionType::Check };
+        game.act(seat, action.into(), None).unwrap();
+        table.apply_engine(&game);
+        check(&table, total);
+        decisions += 1;
+        assert!(decisions <= 45, "hand did not terminate");
+    }
+
+    // Hands everyone else folded end without a showdown
+    if table.game_state() == GameState::Showdown {
+        let mut game = table.to_engine();
+        game.showdown().unwrap();
+        table.apply_engine(&game);
+        check(&table, total);
+    }
+
+    let mut game = table.to_engine();
+    let settlement = game.settle().unwrap();
+    table.apply_engine(&game);
+    assert_eq!(settlement.payouts.iter().sum::<u64>(), settlement.pots.total());
+    assert_eq!(table.game_state(), GameState::Waiting);
+    check(&table, total);
+});
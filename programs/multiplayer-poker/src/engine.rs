@@ -0,0 +1,228 @@
+//! Conversions between the `Table` account and the `poker_engine` rules engine.
+//!
+//! Handlers load the account into an engine table, apply the rules there and
+//! store the result back, so the program and off-chain users of the engine
+//! always agree on the outcome of a hand.
+use anchor_lang::prelude::*;
+use poker_engine as engine;
+
+use crate::error::PokerError;
+use crate::state::*;
+
+impl Table {
+    /// Snapshot of the rules-relevant part of the table
+    pub fn to_engine(&self) -> engine::Table {
+        engine::Table {
+            small_blind: self.small_blind,
+            big_blind: self.big_blind,
+            max_players: self.max_players,
+            game_state: self.game_state().into(),
+            dealer_position: self.dealer_position,
+            small_blind_position: self.small_blind_position,
+            big_blind_position: self.big_blind_position,
+            current_player: self.current_player(),
+            last_aggressor: self.last_aggressor(),
+            pot: self.pot,
+            community_cards: self.community_cards().map(|c| c.map(Into::into)),
+            second_board: self.second_board().map(|board| board.map(|c| c.map(Into::into))),
+            round: self.round().into(),
+            last_raise_amount: self.last_raise_amount,
+            min_raise: self.min_raise,
+            players: core::array::from_fn(|seat| {
+                self.get_player(seat as u8).map(|p| engine::Player {
+                    run_twice: self.run_twice[seat] != 0,
+                    muck: self.mucks(seat as u8),
+                    show: self.show_decision(seat as u8),
+                    ..p.to_engine()
+                })
+            }),
+            player_count: self.player_count,
+            hand_number: self.hand_number,
+            deck_seed: self.deck_seed,
+            deck_position: self.deck_position,
+        }
+    }
+
+    /// Write an engine table back into the account.
+    ///
+    /// Hole card commitments are kept for players whose cards did not change.
+    pub fn apply_engine(&mut self, game: &engine::Table) {
+        self.small_blind = game.small_blind;
+        self.big_blind = game.big_blind;
+        self.max_players = game.max_players;
+        self.set_game_state(game.game_state.into());
+        self.dealer_position = game.dealer_position;
+        self.small_blind_position = game.small_blind_position;
+        self.big_blind_position = game.big_blind_position;
+        self.set_current_player(game.current_player);
+        self.set_last_aggressor(game.last_aggressor);
+        self.pot = game.pot;
+        self.set_community_cards(game.community_cards.map(|c| c.map(Into::into)));
+        self.set_second_board(game.second_board.map(|board| board.map(|c| c.map(Into::into))));
+        self.set_round(game.round.into());
+        self.last_raise_amount = game.last_raise_amount;
+        self.min_raise = game.min_raise;
+        for (seat, player) in game.players.iter().enumerate() {
+            self.run_twice[seat] = player.is_some_and(|p| p.run_twice) as u8;
+            self.muck[seat] = player.is_some_and(|p| p.muck) as u8;
+            let seat = seat as u8;
+            self.set_show_decision(seat, player.and_then(|p| p.show));
+            let previous = self.get_player(seat);
+            let player = player.map(|p| Player::from_engine(seat, &p, previous.as_ref()));
+            self.set_player(seat, player.as_ref());
+        }
+        self.player_count = game.player_count;
+        self.hand_number = game.hand_number;
+        self.deck_seed = game.deck_seed;
+        self.deck_position = game.deck_position;
+    }
+}
+
+impl Player {
+    pub fn to_engine(&self) -> engine::Player {
+        engine::Player {
+            id: self.pubkey.to_bytes(),
+            stack: self.stack,
+            bet: self.bet,
+            hole_cards: self.hole_cards.map(|c| c.map(Into::into)),
+            is_all_in: self.is_all_in,
+            has_folded: self.has_folded,
+            has_acted: self.has_acted,
+            last_action: self.last_action.map(Into::into),
+            // Kept on the table rather than the seat, see `Table::to_engine`
+            run_twice: false,
+            muck: false,
+            show: None,
+        }
+    }
+
+    fn from_engine(seat: u8, player: &engine::Player, previous: Option<&Player>) -> Self {
+        let pubkey = Pubkey::new_from_array(player.id);
+        let hole_cards = player.hole_cards.map(|c| c.map(Into::into));
+        let hole_cards_committed = previous
+            .filter(|p| p.pubkey == pubkey && p.hole_cards == hole_cards)
+            .map_or([0; 32], |p| p.hole_cards_committed);
+        Self {
+            pubkey,
+            seat,
+            stack: player.stack,
+            bet: player.bet,
+            hole_cards,
+            hole_cards_committed,
+            is_all_in: player.is_all_in,
+            has_folded: player.has_folded,
+            has_acted: player.has_acted,
+            last_action: player.last_action.map(Into::into),
+        }
+    }
+}
+
+impl From<Card> for engine::Card {
+    fn from(card: Card) -> Self {
+        engine::Card::from_u8(card.to_u8())
+    }
+}
+
+impl From<engine::Card> for Card {
+    fn from(card: engine::Card) -> Self {
+        Card::from_u8(card.to_u8())
+    }
+}
+
+impl From<GameState> for engine::GameState {
+    fn from(state: GameState) -> Self {
+        match state {
+            GameState::Waiting => engine::GameState::Waiting,
+            GameState::Starting => engine::GameState::Starting,
+            GameState::PreFlop => engine::GameState::PreFlop,
+            GameState::Flop => engine::GameState::Flop,
+            GameState::Turn => engine::GameState::Turn,
+            GameState::River => engine::GameState::River,
+            GameState::Showdown => engine::GameState::Showdown,
+            GameState::Finished => engine::GameState::Finished,
+        }
+    }
+}
+
+impl From<engine::GameState> for GameState {
+    fn from(state: engine::GameState) -> Self {
+        match state {
+            engine::GameState::Waiting => GameState::Waiting,
+            engine::GameState::Starting => GameState::Starting,
+            engine::GameState::PreFlop => GameState::PreFlop,
+            engine::GameState::Flop => GameState::Flop,
+            engine::GameState::Turn => GameState::Turn,
+            engine::GameState::River => GameState::River,
+            engine::GameState::Showdown => GameState::Showdown,
+            engine::GameState::Finished => GameState::Finished,
+        }
+    }
+}
+
+impl From<Round> for engine::Round {
+    fn from(round: Round) -> Self {
+        match round {
+            Round::PreFlop => engine::Round::PreFlop,
+            Round::Flop => engine::Round::Flop,
+            Round::Turn => engine::Round::Turn,
+            Round::River => engine::Round::River,
+        }
+    }
+}
+
+impl From<engine::Round> for Round {
+    fn from(round: engine::Round) -> Self {
+        match round {
+            engine::Round::PreFlop => Round::PreFlop,
+            engine::Round::Flop => Round::Flop,
+            engine::Round::Turn => Round::Turn,
+            engine::Round::River => Round::River,
+        }
+    }
+}
+
+impl From<ActionType> for engine::ActionType {
+    fn from(action: ActionType) -> Self {
+        match action {
+            ActionType::Fold => engine::ActionType::Fold,
+            ActionType::Check => engine::ActionType::Check,
+            ActionType::Call => engine::ActionType::Call,
+            ActionType::Raise => engine::ActionType::Raise,
+            ActionType::AllIn => engine::ActionType::AllIn,
+        }
+    }
+}
+
+impl From<engine::ActionType> for ActionType {
+    fn from(action: engine::ActionType) -> Self {
+        match action {
+            engine::ActionType::Fold => ActionType::Fold,
+            engine::ActionType::Check => ActionType::Check,
+            engine::ActionType::Call => ActionType::Call,
+            engine::ActionType::Raise => ActionType::Raise,
+            engine::ActionType::AllIn => ActionType::AllIn,
+        }
+    }
+}
+
+impl From<engine::EngineError> for PokerError {
+    fn from(err: engine::EngineError) -> Self {
+        match err {
+            engine::EngineError::TableFull => PokerError::TableFull,
+            engine::EngineError::InvalidSeat => PokerError::InvalidSeat,
+            engine::EngineError::SeatOccupied => PokerError::SeatOccupied,
+            engine::EngineError::AlreadySeated => PokerError::AlreadySeated,
+            engine::EngineError::NotEnoughPlayers => PokerError::NotEnoughPlayers,
+            engine::EngineError::GameInProgress => PokerError::GameInProgress,
+            engine::EngineError::NotPlayersTurn => PokerError::NotPlayersTurn,
+            engine::EngineError::AlreadyActed => PokerError::AlreadyActed,
+            engine::EngineError::InvalidAction => PokerError::InvalidAction,
+            engine::EngineError::InsufficientFunds => PokerError::InsufficientFunds,
+            engine::EngineError::RaiseTooSmall => PokerError::RaiseTooSmall,
+            engine::EngineError::PlayerNotFound => PokerError::PlayerNotFound,
+            engine::EngineError::CannotLeaveDuringGame => PokerError::CannotLeaveDuringGame,
+            engine::EngineError::MathOverflow => PokerError::MathOverflow,
+            engine::EngineError::InvalidGameState => PokerError::InvalidGameState,
+        }
+    }
+}
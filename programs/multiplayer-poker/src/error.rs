@@ -58,4 +58,91 @@ pub enum PokerError {
     
     #[msg("Showdown required")]
     ShowdownRequired,
+
+    #[msg("Player profile missing or invalid")]
+    InvalidProfile,
+
+    #[msg("Table still has seated players")]
+    TableNotEmpty,
+
+    #[msg("Table has not been inactive long enough")]
+    TableNotAbandoned,
+
+    #[msg("Unauthorized")]
+    Unauthorized,
+
+    #[msg("Unsupported account version; migrate the account first")]
+    UnsupportedAccountVersion,
+
+    #[msg("Wallet is not invited to this table")]
+    NotInvited,
+
+    #[msg("Wrong table password")]
+    InvalidPassword,
+
+    #[msg("Allowlist is full")]
+    AllowlistFull,
+
+    #[msg("Table is not allowlist-only")]
+    NotAllowlistTable,
+
+    #[msg("Wallet does not hold the tokens this table requires")]
+    TokenGateNotMet,
+
+    #[msg("Table has free seats; join it directly")]
+    TableNotFull,
+
+    #[msg("Already on the waitlist")]
+    AlreadyWaitlisted,
+
+    #[msg("Waitlist is full")]
+    WaitlistFull,
+
+    #[msg("Not on the waitlist")]
+    NotWaitlisted,
+
+    #[msg("Seat is reserved for another player")]
+    SeatReserved,
+
+    #[msg("Waitlisted players are ahead of you")]
+    WaitlistAhead,
+
+    #[msg("Waitlist accounts do not match the queue")]
+    WaitlistMismatch,
+
+    #[msg("Already leaving once the hand ends")]
+    AlreadyLeaving,
+
+    #[msg("Player still has time to act")]
+    ActionNotTimedOut,
+
+    #[msg("Signer is not the player or their session key")]
+    InvalidSessionKey,
+
+    #[msg("Session has expired")]
+    SessionExpired,
+
+    #[msg("Session expiry must be in the future and within the maximum duration")]
+    InvalidSessionExpiry,
+
+    #[msg("Relayer is not the one the table's gas sponsor pays")]
+    InvalidRelayer,
+
+    #[msg("Jackpot drop is too high or its shares do not add up to 100%")]
+    InvalidJackpotConfig,
+
+    #[msg("Table plays for the jackpot but it was not passed")]
+    JackpotRequired,
+
+    #[msg("Players are still waitlisted for this table")]
+    WaitlistNotEmpty,
+
+    #[msg("Wallet held a seat too recently to hold another")]
+    ReservationCooldown,
+
+    #[msg("Player already seated at table")]
+    AlreadySeated,
+
+    #[msg("Sponsored instructions need the instructions sysvar")]
+    InstructionsSysvarRequired,
 }
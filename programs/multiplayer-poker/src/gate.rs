@@ -0,0 +1,85 @@
+//! Token and NFT gates checked by `join_table`.
+//!
+//! Collection membership comes from the Metaplex metadata account of the
+//! NFT's mint. Only the leading fields up to `collection` are decoded, which
+//! keeps the metadata program's crate out of the build.
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+use crate::error::PokerError;
+use crate::state::TokenGate;
+
+/// Metaplex Token Metadata program
+pub const TOKEN_METADATA_PROGRAM_ID: Pubkey = pubkey!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct Creator {
+    pub address: Pubkey,
+    pub verified: bool,
+    pub share: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub struct Collection {
+    pub verified: bool,
+    pub key: Pubkey,
+}
+
+/// Prefix of a Metaplex `Metadata` account, up to and including `collection`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct Metadata {
+    pub key: u8,
+    pub update_authority: Pubkey,
+    pub mint: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub seller_fee_basis_points: u16,
+    pub creators: Option<Vec<Creator>>,
+    pub primary_sale_happened: bool,
+    pub is_mutable: bool,
+    pub edition_nonce: Option<u8>,
+    pub token_standard: Option<u8>,
+    pub collection: Option<Collection>,
+}
+
+pub fn metadata_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"metadata", TOKEN_METADATA_PROGRAM_ID.as_ref(), mint.as_ref()],
+        &TOKEN_METADATA_PROGRAM_ID,
+    )
+    .0
+}
+
+/// Check that `player` holds what `gate` asks for. `metadata` is only read
+/// for collection gates.
+pub fn check(
+    gate: &TokenGate,
+    player: &Pubkey,
+    token_account: Option<&TokenAccount>,
+    metadata: Option<&AccountInfo>,
+) -> Result<()> {
+    let token_account = token_account.ok_or(PokerError::TokenGateNotMet)?;
+    require_keys_eq!(token_account.owner, *player, PokerError::TokenGateNotMet);
+
+    match gate {
+        TokenGate::Token { mint, min_balance } => {
+            require_keys_eq!(token_account.mint, *mint, PokerError::TokenGateNotMet);
+            require!(token_account.amount >= *min_balance, PokerError::TokenGateNotMet);
+        }
+        TokenGate::Collection { collection } => {
+            require!(token_account.amount >= 1, PokerError::TokenGateNotMet);
+            let metadata = metadata.ok_or(PokerError::TokenGateNotMet)?;
+            require_keys_eq!(*metadata.owner, TOKEN_METADATA_PROGRAM_ID, PokerError::TokenGateNotMet);
+            require_keys_eq!(metadata.key(), metadata_address(&token_account.mint), PokerError::TokenGateNotMet);
+
+            let data = metadata.try_borrow_data()?;
+            let metadata = Metadata::deserialize(&mut &data[..]).map_err(|_| PokerError::TokenGateNotMet)?;
+            require!(
+                metadata.collection.is_some_and(|c| c.verified && c.key == *collection),
+                PokerError::TokenGateNotMet
+            );
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::PokerError;
+
+#[derive(Accounts)]
+pub struct AddInvitee<'info> {
+    #[account(
+        has_one = creator,
+        seeds = [Table::SEED, table.load_current()?.creator.as_ref(), &table.load_current()?.table_id.to_le_bytes()],
+        bump = table.load_current()?.bump
+    )]
+    pub table: AccountLoader<'info, Table>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = TableAllowlist::MAX_SIZE,
+        seeds = [TableAllowlist::SEED, table.key().as_ref()],
+        bump
+    )]
+    pub allowlist: Account<'info, TableAllowlist>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<AddInvitee>, invitee: Pubkey) -> Result<()> {
+    let table = ctx.accounts.table.load()?;
+    require!(table.access() == TableAccess::Allowlist, PokerError::NotAllowlistTable);
+    require!(table.game_state() == GameState::Waiting, PokerError::GameInProgress);
+
+    // The first invitation creates the allowlist
+    let allowlist = &mut ctx.accounts.allowlist;
+    if allowlist.table == Pubkey::default() {
+        allowlist.version = TableAllowlist::VERSION;
+        allowlist.table = ctx.accounts.table.key();
+        allowlist.bump = ctx.bumps.allowlist;
+    }
+    require!(allowlist.version == TableAllowlist::VERSION, PokerError::UnsupportedAccountVersion);
+
+    if !allowlist.invitees.contains(&invitee) {
+        require!(allowlist.invitees.len() < TableAllowlist::MAX_INVITEES, PokerError::AllowlistFull);
+        allowlist.invitees.push(invitee);
+    }
+
+    msg!("Invited {} to table {}", invitee, table.table_id);
+    Ok(())
+}
@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct CloseGasSponsor<'info> {
+    #[account(
+        has_one = creator,
+        seeds = [Table::SEED, table.load_current()?.creator.as_ref(), &table.load_current()?.table_id.to_le_bytes()],
+        bump = table.load_current()?.bump
+    )]
+    pub table: AccountLoader<'info, Table>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        close = creator,
+        seeds = [GasSponsor::SEED, table.key().as_ref()],
+        bump = sponsor.bump
+    )]
+    pub sponsor: Account<'info, GasSponsor>,
+}
+
+pub fn handler(ctx: Context<CloseGasSponsor>) -> Result<()> {
+    let table = ctx.accounts.table.load()?;
+    msg!("Gas sponsor for table {} closed", table.table_id);
+    Ok(())
+}
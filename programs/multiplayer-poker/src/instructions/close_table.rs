@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::PokerError;
+
+#[derive(Accounts)]
+pub struct CloseTable<'info> {
+    #[account(
+        mut,
+        has_one = creator,
+        close = creator,
+        seeds = [Table::SEED, table.load_current()?.creator.as_ref(), &table.load_current()?.table_id.to_le_bytes()],
+        bump = table.load_current()?.bump
+    )]
+    pub table: AccountLoader<'info, Table>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    // The table's other PDAs can't be closed once it is gone, so pass any
+    // that exist to get their rent back too
+    #[account(
+        mut,
+        close = creator,
+        seeds = [TableWaitlist::SEED, table.key().as_ref()],
+        bump = waitlist.bump
+    )]
+    pub waitlist: Option<Account<'info, TableWaitlist>>,
+
+    #[account(
+        mut,
+        close = creator,
+        seeds = [TableAllowlist::SEED, table.key().as_ref()],
+        bump = allowlist.bump
+    )]
+    pub allowlist: Option<Account<'info, TableAllowlist>>,
+
+    #[account(
+        mut,
+        close = creator,
+        seeds = [GasSponsor::SEED, table.key().as_ref()],
+        bump = sponsor.bump
+    )]
+    pub sponsor: Option<Account<'info, GasSponsor>>,
+}
+
+pub fn handler(ctx: Context<CloseTable>) -> Result<()> {
+    let table = ctx.accounts.table.load_current()?;
+
+    // Stacks are only recorded on the table, so closing with players seated would erase them
+    require!(table.player_count == 0, PokerError::TableNotEmpty);
+    require!(table.game_state() == GameState::Waiting, PokerError::GameInProgress);
+    // Waitlist deposits are held for the table, so they have to be claimed back first
+    require!(table.waitlist_len == 0, PokerError::WaitlistNotEmpty);
+
+    emit!(TableClosedEvent {
+        table_id: table.table_id,
+        creator: table.creator,
+        forced: false,
+    });
+
+    msg!("Table {} closed", table.table_id);
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::PokerError;
+
+#[derive(Accounts)]
+pub struct CreateSession<'info> {
+    #[account(
+        seeds = [Table::SEED, table.load_current()?.creator.as_ref(), &table.load_current()?.table_id.to_le_bytes()],
+        bump = table.load_current()?.bump
+    )]
+    pub table: AccountLoader<'info, Table>,
+
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    /// One session per player and table; creating another replaces it
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = SessionToken::MAX_SIZE,
+        seeds = [SessionToken::SEED, table.key().as_ref(), player.key().as_ref()],
+        bump
+    )]
+    pub session: Account<'info, SessionToken>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<CreateSession>, session_key: Pubkey, expires_at: i64) -> Result<()> {
+    let table = ctx.accounts.table.load()?;
+    let player_key = ctx.accounts.player.key();
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(
+        expires_at > now && expires_at - now <= SessionToken::MAX_DURATION,
+        PokerError::InvalidSessionExpiry
+    );
+    require_keys_neq!(session_key, player_key, PokerError::InvalidSessionKey);
+
+    let session = &mut ctx.accounts.session;
+    session.version = SessionToken::VERSION;
+    session.table = ctx.accounts.table.key();
+    session.player = player_key;
+    session.session_key = session_key;
+    session.expires_at = expires_at;
+    session.bump = ctx.bumps.session;
+
+    emit!(SessionStartedEvent {
+        table_id: table.table_id,
+        player: player_key,
+        session_key,
+        expires_at,
+    });
+
+    msg!("Player {} started a session at table {}", player_key, table.table_id);
+    Ok(())
+}
@@ -9,10 +9,10 @@ pub struct CreateTable<'info> {
         init,
         payer = creator,
         space = Table::MAX_SIZE,
-        seeds = [b"table", creator.key().as_ref(), &table_id.to_le_bytes()],
+        seeds = [Table::SEED, creator.key().as_ref(), &table_id.to_le_bytes()],
         bump
     )]
-    pub table: Account<'info, Table>,
+    pub table: AccountLoader<'info, Table>,
     
     #[account(mut)]
     pub creator: Signer<'info>,
@@ -20,6 +20,7 @@ pub struct CreateTable<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn handler(
     ctx: Context<CreateTable>,
     table_id: u64,
@@ -27,35 +28,47 @@ pub fn handler(
     big_blind: u64,
     max_players: u8,
     buy_in: u64,
+    access: TableAccess,
+    gate: Option<TokenGate>,
 ) -> Result<()> {
-    require!(max_players >= 2 && max_players <= 9, PokerError::InvalidSeat);
+    require!((2..=9).contains(&max_players), PokerError::InvalidSeat);
     require!(big_blind > small_blind, PokerError::InvalidAction);
     require!(buy_in >= big_blind * 10, PokerError::InvalidAction); // Minimum 10 big blinds
+    if let Some(TokenGate::Token { min_balance, .. }) = gate {
+        require!(min_balance > 0, PokerError::InvalidAction);
+    }
 
-    let table = &mut ctx.accounts.table;
+    let mut table = ctx.accounts.table.load_init()?;
     let clock = Clock::get()?;
 
+    table.version = Table::VERSION;
     table.creator = ctx.accounts.creator.key();
     table.table_id = table_id;
     table.small_blind = small_blind;
     table.big_blind = big_blind;
     table.max_players = max_players;
     table.buy_in = buy_in;
-    table.game_state = GameState::Waiting;
+    table.set_game_state(GameState::Waiting);
     table.dealer_position = 0;
     table.small_blind_position = 0;
     table.big_blind_position = 0;
-    table.current_player = None;
+    table.set_current_player(None);
     table.pot = 0;
-    table.community_cards = [None; 5];
-    table.round = Round::PreFlop;
+    table.set_community_cards([None; 5]);
+    table.set_round(Round::PreFlop);
     table.last_raise_amount = 0;
     table.min_raise = big_blind;
-    table.players = [None; 9];
+    table.players = [Seat::default(); 9];
     table.player_count = 0;
     table.created_at = clock.unix_timestamp;
     table.last_action_at = clock.unix_timestamp;
     table.action_timeout = 60; // 60 seconds
+    table.hand_number = 0;
+    table.deck_seed = [0; 32];
+    table.deck_position = 0;
+    table.set_access(access);
+    table.set_gate(gate);
+    table.bump = ctx.bumps.table;
 
     msg!("Table created: {}", table_id);
     Ok(())
@@ -1,74 +1,194 @@
 use anchor_lang::prelude::*;
 use crate::state::*;
 use crate::error::PokerError;
+use crate::instructions::leave_table::offer_seat_to_waitlist;
+use crate::instructions::set_gas_sponsor::reimburse_relayer;
 
 #[derive(Accounts)]
 pub struct DistributePot<'info> {
+    // Permissionless: the outcome depends only on the table state
+    #[account(
+        mut,
+        seeds = [Table::SEED, table.load_current()?.creator.as_ref(), &table.load_current()?.table_id.to_le_bytes()],
+        bump = table.load_current()?.bump
+    )]
+    pub table: AccountLoader<'info, Table>,
+
+    // Required when the table plays for the jackpot
+    #[account(mut, seeds = [Jackpot::SEED], bump = jackpot.bump)]
+    pub jackpot: Option<Account<'info, Jackpot>>,
+
+    // Required while anyone is waitlisted, to hand on seats freed by leavers
+    #[account(
+        mut,
+        seeds = [TableWaitlist::SEED, table.key().as_ref()],
+        bump = waitlist.bump
+    )]
+    pub waitlist: Option<Account<'info, TableWaitlist>>,
+
+    /// Fee payer submitting the crank, paid back by `sponsor`
     #[account(mut)]
-    pub table: Account<'info, Table>,
-    
-    pub authority: Signer<'info>,
+    pub relayer: Option<Signer<'info>>,
+
+    #[account(
+        mut,
+        seeds = [GasSponsor::SEED, table.key().as_ref()],
+        bump = sponsor.bump
+    )]
+    pub sponsor: Option<Account<'info, GasSponsor>>,
+
+    /// CHECK: the instructions sysvar, required with `sponsor`
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: Option<UncheckedAccount<'info>>,
 }
 
-pub fn handler(ctx: Context<DistributePot>) -> Result<()> {
-    let table = &mut ctx.accounts.table;
-
-    require!(table.game_state == GameState::Finished, PokerError::InvalidGameState);
-
-    // Simplified winner determination - in production, implement proper hand ranking
-    let active_players = table.get_active_players();
-    
-    if active_players.len() == 1 {
-        // Only one player left - they win
-        let winner = active_players[0].1.pubkey;
-        let pot = table.pot;
-        
-        // In production, transfer SOL from escrow to winner
-        // For now, just update stack
-        if let Some(player) = table.get_player_mut(active_players[0].0) {
-            player.stack = player.stack.checked_add(pot).ok_or(PokerError::MathOverflow)?;
-        }
+/// Remaining accounts are the profiles of everyone dealt in, in seat order,
+/// then the wallets of waitlisted depositors that freed seats go to
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, DistributePot<'info>>) -> Result<()> {
+    let mut table = ctx.accounts.table.load_mut()?;
+    let hand = table.players();
 
-        emit!(PotDistributedEvent {
-            table_id: table.table_id,
-            winners: vec![winner],
-            amounts: vec![pot],
-        });
+    let jackpot = if table.jackpot != 0 {
+        Some(ctx.accounts.jackpot.as_mut().ok_or(PokerError::JackpotRequired)?)
     } else {
-        // Multiple players - need hand evaluation (simplified: first active player wins)
-        // In production, implement proper poker hand ranking
-        let winner = active_players[0].1.pubkey;
-        let pot = table.pot;
-        
-        if let Some(player) = table.get_player_mut(active_players[0].0) {
-            player.stack = player.stack.checked_add(pot).ok_or(PokerError::MathOverflow)?;
+        None
+    };
+    if let Some(jackpot) = &jackpot {
+        require!(jackpot.version == Jackpot::VERSION, PokerError::UnsupportedAccountVersion);
+    }
+
+    // Pay out main and side pots less the jackpot drop, then reset for next game
+    let mut game = table.to_engine();
+    let bad_beat = jackpot.as_ref().and_then(|_| game.bad_beat());
+    let drop_bps = jackpot.as_ref().map_or(0, |j| j.drop_bps);
+    let settlement = game.settle_with_drop(drop_bps).map_err(PokerError::from)?;
+
+    // Players dealt into the hand, in seat order, and whether it was contested
+    let dealt: Vec<(usize, Player)> = hand
+        .iter()
+        .enumerate()
+        .filter_map(|(seat, p)| p.filter(|p| p.hole_cards[0].is_some()).map(|p| (seat, p)))
+        .collect();
+    let showdown = dealt.iter().filter(|(_, p)| !p.has_folded).count() > 1;
+
+    require!(ctx.remaining_accounts.len() >= dealt.len(), PokerError::InvalidProfile);
+    let (profiles, mut wallets) = (&ctx.remaining_accounts[..dealt.len()], ctx.remaining_accounts[dealt.len()..].iter());
+    for ((seat, player), info) in dealt.iter().zip(profiles) {
+        require!(info.is_writable, PokerError::InvalidProfile);
+        // Players seated on a migrated legacy table have no profile until they next join
+        if info.data_is_empty() {
+            let (address, _) =
+                Pubkey::find_program_address(&[PlayerProfile::SEED, player.pubkey.as_ref()], &crate::ID);
+            require_keys_eq!(info.key(), address, PokerError::InvalidProfile);
+            continue;
         }
+        let mut profile = Account::<PlayerProfile>::try_from(info)?;
+        require_keys_eq!(profile.player, player.pubkey, PokerError::InvalidProfile);
+        require!(profile.version == PlayerProfile::VERSION, PokerError::UnsupportedAccountVersion);
+        let preflop = table.preflop_stats[*seat];
+        profile.record_hand(player.bet, settlement.payouts[*seat], showdown && !player.has_folded, preflop)?;
+        profile.exit(&crate::ID)?;
+    }
 
-        emit!(PotDistributedEvent {
-            table_id: table.table_id,
-            winners: vec![winner],
-            amounts: vec![pot],
-        });
+    if let Some(jackpot) = jackpot {
+        jackpot.balance = jackpot.balance.checked_add(settlement.drop).ok_or(PokerError::MathOverflow)?;
+        if let Some(beat) = bad_beat {
+            pay_bad_beat(jackpot, &mut game, &dealt, beat, table.table_id)?;
+        }
     }
 
-    // Reset for next game
-    table.pot = 0;
-    table.game_state = GameState::Waiting;
-    table.community_cards = [None; 5];
-    table.current_player = None;
-    
-    // Reset player bets and status
-    for player_opt in &mut table.players {
-        if let Some(player) = player_opt {
-            player.bet = 0;
-            player.has_folded = false;
-            player.has_acted = false;
-            player.is_all_in = false;
-            player.hole_cards = [None; 2];
-            player.hole_cards_committed = [0; 32];
+    // In production, transfer SOL from escrow to winners
+    // For now, just update stacks
+    let mut winners = Vec::new();
+    let mut amounts = Vec::new();
+    for (seat, &amount) in settlement.payouts.iter().enumerate() {
+        if amount == 0 {
+            continue;
+        }
+        if let Some(player) = &game.players[seat] {
+            winners.push(Pubkey::new_from_array(player.id));
+            amounts.push(amount);
         }
     }
 
+    // Cash out players who left during the hand
+    let mut departed = Vec::new();
+    for seat in 0..game.players.len() as u8 {
+        if table.is_leaving(seat) {
+            let player = game.unseat_player(seat).map_err(PokerError::from)?;
+            departed.push((seat, player));
+        }
+    }
+    table.apply_engine(&game);
+    table.leaving = [0; 9];
+
+    if !departed.is_empty() && table.waitlist_len > 0 {
+        let waitlist = ctx.accounts.waitlist.as_mut().ok_or(PokerError::WaitlistMismatch)?;
+        let now = Clock::get()?.unix_timestamp;
+        for (seat, _) in &departed {
+            offer_seat_to_waitlist(&mut table, *seat, waitlist, &mut wallets, now)?;
+        }
+    }
+
+    emit!(PotDistributedEvent {
+        table_id: table.table_id,
+        winners,
+        amounts,
+    });
+
+    // Refund stack (simplified - in production transfer from escrow)
+    for (seat, player) in departed {
+        emit!(PlayerLeftEvent {
+            table_id: table.table_id,
+            player: Pubkey::new_from_array(player.id),
+            seat,
+            stack: player.stack,
+        });
+    }
+
+    reimburse_relayer(
+        table.table_id,
+        ctx.accounts.sponsor.as_ref(),
+        ctx.accounts.relayer.as_ref(),
+        ctx.accounts.instructions.as_deref(),
+    )?;
+
     msg!("Pot distributed at table {}", table.table_id);
     Ok(())
 }
+
+/// Share the jackpot between the loser, the winner and everyone else dealt in
+fn pay_bad_beat(
+    jackpot: &mut Jackpot,
+    game: &mut poker_engine::Table,
+    dealt: &[(usize, Player)],
+    beat: poker_engine::BadBeat,
+    table_id: u64,
+) -> Result<()> {
+    let others: Vec<usize> = dealt
+        .iter()
+        .map(|(seat, _)| *seat)
+        .filter(|&seat| seat != beat.loser as usize && seat != beat.winner as usize)
+        .collect();
+    let (loser_amount, winner_amount, table_amount) = jackpot.shares(others.len() as u64);
+
+    let mut paid = 0u64;
+    let payouts = [(beat.loser as usize, loser_amount), (beat.winner as usize, winner_amount)];
+    for (seat, amount) in payouts.into_iter().chain(others.iter().map(|&seat| (seat, table_amount))) {
+        let player = game.players[seat].as_mut().ok_or(PokerError::PlayerNotFound)?;
+        player.stack = player.stack.checked_add(amount).ok_or(PokerError::MathOverflow)?;
+        paid = paid.checked_add(amount).ok_or(PokerError::MathOverflow)?;
+    }
+    jackpot.balance = jackpot.balance.checked_sub(paid).ok_or(PokerError::MathOverflow)?;
+
+    let player = |seat: u8| game.players[seat as usize].map_or(Pubkey::default(), |p| Pubkey::new_from_array(p.id));
+    emit!(JackpotPaidEvent {
+        table_id,
+        loser: player(beat.loser),
+        winner: player(beat.winner),
+        loser_amount,
+        winner_amount,
+        table_amount,
+    });
+    Ok(())
+}
@@ -0,0 +1,92 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+use crate::state::*;
+use crate::error::PokerError;
+
+#[derive(Accounts)]
+pub struct ForceCloseTable<'info> {
+    #[account(
+        mut,
+        has_one = creator,
+        close = creator,
+        seeds = [Table::SEED, table.load_current()?.creator.as_ref(), &table.load_current()?.table_id.to_le_bytes()],
+        bump = table.load_current()?.bump
+    )]
+    pub table: AccountLoader<'info, Table>,
+
+    /// CHECK: receives the rent, matched against `table.creator`
+    #[account(mut)]
+    pub creator: UncheckedAccount<'info>,
+
+    pub admin: Signer<'info>,
+
+    /// The program's own ProgramData, whose upgrade authority acts as admin
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = bpf_loader_upgradeable::ID,
+        constraint = program_data.upgrade_authority_address == Some(admin.key()) @ PokerError::Unauthorized
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    /// Required while anyone is waitlisted, so their deposits can be refunded
+    #[account(
+        mut,
+        close = creator,
+        seeds = [TableWaitlist::SEED, table.key().as_ref()],
+        bump = waitlist.bump
+    )]
+    pub waitlist: Option<Account<'info, TableWaitlist>>,
+
+    #[account(
+        mut,
+        close = creator,
+        seeds = [TableAllowlist::SEED, table.key().as_ref()],
+        bump = allowlist.bump
+    )]
+    pub allowlist: Option<Account<'info, TableAllowlist>>,
+
+    #[account(
+        mut,
+        close = creator,
+        seeds = [GasSponsor::SEED, table.key().as_ref()],
+        bump = sponsor.bump
+    )]
+    pub sponsor: Option<Account<'info, GasSponsor>>,
+}
+
+/// Remaining accounts are the wallets of waitlisted depositors, in queue order
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, ForceCloseTable<'info>>) -> Result<()> {
+    let table = ctx.accounts.table.load_current()?;
+    let clock = Clock::get()?;
+
+    require!(
+        clock.unix_timestamp - table.last_action_at >= Table::ABANDON_TIMEOUT,
+        PokerError::TableNotAbandoned
+    );
+
+    if table.waitlist_len > 0 {
+        let waitlist = ctx.accounts.waitlist.as_mut().ok_or(PokerError::WaitlistMismatch)?;
+        let mut wallets = ctx.remaining_accounts.iter();
+        while let Some(entry) = waitlist.entries.first().copied() {
+            if entry.deposit == 0 {
+                waitlist.entries.remove(0);
+                continue;
+            }
+            let wallet = wallets
+                .next()
+                .filter(|w| w.key() == entry.player && w.is_writable)
+                .ok_or(PokerError::WaitlistMismatch)?;
+            TableWaitlist::remove_entry(waitlist, 0, wallet)?;
+        }
+    }
+
+    emit!(TableClosedEvent {
+        table_id: table.table_id,
+        creator: table.creator,
+        forced: true,
+    });
+
+    msg!("Abandoned table {} closed with {} players seated", table.table_id, table.player_count);
+    Ok(())
+}
@@ -1,64 +1,147 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use crate::gate;
 use crate::state::*;
 use crate::error::PokerError;
 
 #[derive(Accounts)]
 pub struct JoinTable<'info> {
-    #[account(mut)]
-    pub table: Account<'info, Table>,
+    #[account(
+        mut,
+        seeds = [Table::SEED, table.load_current()?.creator.as_ref(), &table.load_current()?.table_id.to_le_bytes()],
+        bump = table.load_current()?.bump
+    )]
+    pub table: AccountLoader<'info, Table>,
     
     #[account(mut)]
     pub player: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = PlayerProfile::MAX_SIZE,
+        seeds = [PlayerProfile::SEED, player.key().as_ref()],
+        bump
+    )]
+    pub profile: Account<'info, PlayerProfile>,
+
+    /// Required to join an allowlist table
+    #[account(
+        seeds = [TableAllowlist::SEED, table.key().as_ref()],
+        bump = allowlist.bump
+    )]
+    pub allowlist: Option<Account<'info, TableAllowlist>>,
+
+    /// Required to join a token-gated table; must belong to `player`
+    pub gate_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: metadata of the NFT in `gate_token_account`, for collection gates; verified in `gate::check`
+    pub gate_metadata: Option<UncheckedAccount<'info>>,
+
+    /// Required while anyone is waitlisted
+    #[account(
+        mut,
+        seeds = [TableWaitlist::SEED, table.key().as_ref()],
+        bump = waitlist.bump
+    )]
+    pub waitlist: Option<Account<'info, TableWaitlist>>,
     
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<JoinTable>, seat: u8) -> Result<()> {
-    require!(seat < 9, PokerError::InvalidSeat);
-    require!(ctx.accounts.table.game_state == GameState::Waiting, PokerError::GameInProgress);
-    require!(ctx.accounts.table.player_count < ctx.accounts.table.max_players, PokerError::TableFull);
-    
-    // Check if seat is already occupied
-    if let Some(_) = ctx.accounts.table.players[seat as usize] {
-        return Err(PokerError::SeatOccupied.into());
-    }
+pub fn handler(ctx: Context<JoinTable>, seat: Option<u8>, password: Option<Vec<u8>>) -> Result<()> {
+    let table_key = ctx.accounts.table.key();
+    let mut table = ctx.accounts.table.load_mut()?;
+    let player_key = ctx.accounts.player.key();
+    let now = Clock::get()?.unix_timestamp;
+
+    check_admission(
+        &table,
+        &table_key,
+        &player_key,
+        password,
+        ctx.accounts.allowlist.as_deref(),
+        ctx.accounts.gate_token_account.as_deref(),
+        ctx.accounts.gate_metadata.as_ref().map(AsRef::as_ref),
+    )?;
+    let seat = match seat {
+        Some(seat) => seat,
+        None => table.first_free_seat(&player_key, now).ok_or(PokerError::TableFull)?,
+    };
+    require!(seat < table.max_players, PokerError::InvalidSeat);
 
-    // Check if player already at table
-    for player_opt in &ctx.accounts.table.players {
-        if let Some(player) = player_opt {
-            if player.pubkey == ctx.accounts.player.key() {
-                return Err(PokerError::InvalidAction.into());
-            }
+    // A reserved seat goes to its holder; otherwise queued players come first.
+    // Any of them may take an unheld seat, so a head of the queue who never
+    // turns up doesn't keep it empty.
+    match table.reservation(seat, now) {
+        Some(holder) => {
+            require_keys_eq!(holder, player_key, PokerError::SeatReserved);
+            table.clear_reservation(seat);
         }
+        None if table.waitlist_len > 0 => {
+            let waitlist = ctx.accounts.waitlist.as_mut().ok_or(PokerError::WaitlistAhead)?;
+            let position = waitlist.position(&player_key).ok_or(PokerError::WaitlistAhead)?;
+            TableWaitlist::remove_entry(waitlist, position, &ctx.accounts.player.to_account_info())?;
+            table.waitlist_len -= 1;
+        }
+        None => {}
     }
 
+    ctx.accounts.profile.init_if_new(player_key, ctx.bumps.profile)?;
+
     // Transfer buy-in from player to table (simplified - in production use escrow)
     // For now, we'll just record the stack
-    let buy_in = ctx.accounts.table.buy_in;
-    
-    let player = Player {
-        pubkey: ctx.accounts.player.key(),
-        seat,
-        stack: buy_in,
-        bet: 0,
-        hole_cards: [None; 2],
-        hole_cards_committed: [0; 32],
-        is_all_in: false,
-        has_folded: false,
-        has_acted: false,
-        last_action: None,
-    };
+    let buy_in = table.buy_in;
 
-    ctx.accounts.table.players[seat as usize] = Some(player);
-    ctx.accounts.table.player_count += 1;
+    let mut game = table.to_engine();
+    game.seat_player(seat, player_key.to_bytes(), buy_in).map_err(PokerError::from)?;
+    table.apply_engine(&game);
+    table.reset_time_bank(seat);
+    table.set_muck(seat, false);
+
+    // Seat changes between hands count as activity for abandoned-table checks
+    if table.game_state() == GameState::Waiting {
+        table.last_action_at = now;
+    }
 
     emit!(PlayerJoinedEvent {
-        table_id: ctx.accounts.table.table_id,
-        player: ctx.accounts.player.key(),
+        table_id: table.table_id,
+        player: player_key,
         seat,
         stack: buy_in,
     });
 
-    msg!("Player {} joined table {} at seat {}", ctx.accounts.player.key(), ctx.accounts.table.table_id, seat);
+    msg!("Player {} joined table {} at seat {}", player_key, table.table_id, seat);
+    Ok(())
+}
+
+/// Private-table and token-gate checks, shared with `join_waitlist`.
+/// The creator may always sit at their own table.
+pub fn check_admission(
+    table: &Table,
+    table_key: &Pubkey,
+    player: &Pubkey,
+    password: Option<Vec<u8>>,
+    allowlist: Option<&TableAllowlist>,
+    token_account: Option<&TokenAccount>,
+    metadata: Option<&AccountInfo>,
+) -> Result<()> {
+    if *player == table.creator {
+        return Ok(());
+    }
+    match table.access() {
+        TableAccess::Public => {}
+        TableAccess::Allowlist => {
+            let allowlist = allowlist.ok_or(PokerError::NotInvited)?;
+            require!(allowlist.invitees.contains(player), PokerError::NotInvited);
+        }
+        TableAccess::Password { hash } => {
+            let password = password.ok_or(PokerError::InvalidPassword)?;
+            require!(Table::password_commitment(table_key, &password) == hash, PokerError::InvalidPassword);
+        }
+    }
+    if let Some(token_gate) = table.gate() {
+        gate::check(&token_gate, player, token_account, metadata)?;
+    }
     Ok(())
 }
@@ -0,0 +1,111 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token::TokenAccount;
+use crate::instructions::join_table::check_admission;
+use crate::state::*;
+use crate::error::PokerError;
+
+#[derive(Accounts)]
+pub struct JoinWaitlist<'info> {
+    #[account(
+        mut,
+        seeds = [Table::SEED, table.load_current()?.creator.as_ref(), &table.load_current()?.table_id.to_le_bytes()],
+        bump = table.load_current()?.bump
+    )]
+    pub table: AccountLoader<'info, Table>,
+
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    /// Created here so the player can be seated without signing again
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = PlayerProfile::MAX_SIZE,
+        seeds = [PlayerProfile::SEED, player.key().as_ref()],
+        bump
+    )]
+    pub profile: Account<'info, PlayerProfile>,
+
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = TableWaitlist::MAX_SIZE,
+        seeds = [TableWaitlist::SEED, table.key().as_ref()],
+        bump
+    )]
+    pub waitlist: Account<'info, TableWaitlist>,
+
+    #[account(
+        seeds = [TableAllowlist::SEED, table.key().as_ref()],
+        bump = allowlist.bump
+    )]
+    pub allowlist: Option<Account<'info, TableAllowlist>>,
+
+    pub gate_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: verified in `gate::check`
+    pub gate_metadata: Option<UncheckedAccount<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<JoinWaitlist>, deposit: bool, password: Option<Vec<u8>>) -> Result<()> {
+    let table_key = ctx.accounts.table.key();
+    let mut table = ctx.accounts.table.load_mut()?;
+    let player_key = ctx.accounts.player.key();
+
+    check_admission(
+        &table,
+        &table_key,
+        &player_key,
+        password,
+        ctx.accounts.allowlist.as_deref(),
+        ctx.accounts.gate_token_account.as_deref(),
+        ctx.accounts.gate_metadata.as_ref().map(AsRef::as_ref),
+    )?;
+    require!(
+        table.player_count >= table.max_players || table.waitlist_len > 0,
+        PokerError::TableNotFull
+    );
+    require!(table.find_seat(&player_key).is_none(), PokerError::AlreadySeated);
+
+    ctx.accounts.profile.init_if_new(player_key, ctx.bumps.profile)?;
+
+    // The first wallet to queue creates the waitlist
+    let waitlist = &mut ctx.accounts.waitlist;
+    if waitlist.table == Pubkey::default() {
+        waitlist.version = TableWaitlist::VERSION;
+        waitlist.table = table_key;
+        waitlist.bump = ctx.bumps.waitlist;
+    }
+    require!(waitlist.version == TableWaitlist::VERSION, PokerError::UnsupportedAccountVersion);
+    require!(waitlist.position(&player_key).is_none(), PokerError::AlreadyWaitlisted);
+    require!(waitlist.entries.len() < TableWaitlist::MAX_ENTRIES, PokerError::WaitlistFull);
+
+    let deposit = if deposit { table.buy_in } else { 0 };
+    if deposit > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.player.to_account_info(),
+                    to: waitlist.to_account_info(),
+                },
+            ),
+            deposit,
+        )?;
+    }
+    waitlist.entries.push(WaitlistEntry { player: player_key, deposit });
+    table.waitlist_len = waitlist.entries.len() as u8;
+
+    emit!(WaitlistJoinedEvent {
+        table_id: table.table_id,
+        player: player_key,
+        position: table.waitlist_len - 1,
+        deposit,
+    });
+
+    msg!("Player {} waitlisted at table {} in position {}", player_key, table.table_id, table.waitlist_len);
+    Ok(())
+}
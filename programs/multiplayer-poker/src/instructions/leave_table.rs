@@ -4,42 +4,161 @@ use crate::error::PokerError;
 
 #[derive(Accounts)]
 pub struct LeaveTable<'info> {
-    #[account(mut)]
-    pub table: Account<'info, Table>,
+    #[account(
+        mut,
+        seeds = [Table::SEED, table.load_current()?.creator.as_ref(), &table.load_current()?.table_id.to_le_bytes()],
+        bump = table.load_current()?.bump
+    )]
+    pub table: AccountLoader<'info, Table>,
     
     #[account(mut)]
     pub player: Signer<'info>,
+
+    /// Required while anyone is waitlisted, to hand the seat on
+    #[account(
+        mut,
+        seeds = [TableWaitlist::SEED, table.key().as_ref()],
+        bump = waitlist.bump
+    )]
+    pub waitlist: Option<Account<'info, TableWaitlist>>,
+
+    /// CHECK: wallet of the first waitlisted player, which gets their deposit back when seated
+    #[account(mut)]
+    pub next_player: Option<UncheckedAccount<'info>>,
     
     pub system_program: Program<'info, System>,
 }
 
 pub fn handler(ctx: Context<LeaveTable>) -> Result<()> {
-    require!(
-        ctx.accounts.table.game_state == GameState::Waiting || 
-        ctx.accounts.table.game_state == GameState::Finished,
-        PokerError::CannotLeaveDuringGame
-    );
-
+    let mut table = ctx.accounts.table.load_mut()?;
     let player_key = ctx.accounts.player.key();
-    let mut player_seat: Option<u8> = None;
-
-    // Find player
-    for (seat, player_opt) in ctx.accounts.table.players.iter().enumerate() {
-        if let Some(player) = player_opt {
-            if player.pubkey == player_key {
-                player_seat = Some(seat as u8);
-                break;
-            }
-        }
-    }
 
-    let seat = player_seat.ok_or(PokerError::PlayerNotFound)?;
+    let seat = table.find_seat(&player_key).ok_or(PokerError::PlayerNotFound)?;
+
+    // Until `distribute_pot` settles the hand, fold if needed and leave then
+    if table.game_state() != GameState::Waiting {
+        return leave_after_hand(&mut table, seat, player_key);
+    }
 
     // Remove player
-    ctx.accounts.table.players[seat as usize] = None;
-    ctx.accounts.table.player_count = ctx.accounts.table.player_count.checked_sub(1).ok_or(PokerError::MathOverflow)?;
+    let mut game = table.to_engine();
+    let player = game.unseat_player(seat).map_err(PokerError::from)?;
+    table.apply_engine(&game);
+    table.set_leaving(seat, false);
+
+    // Seat changes between hands count as activity for abandoned-table checks
+    let now = Clock::get()?.unix_timestamp;
+    table.last_action_at = now;
+
+    if table.waitlist_len > 0 {
+        let waitlist = ctx.accounts.waitlist.as_mut().ok_or(PokerError::WaitlistMismatch)?;
+        offer_seat_to_waitlist(&mut table, seat, waitlist, &mut ctx.accounts.next_player.as_deref().into_iter(), now)?;
+    }
 
     // Refund stack (simplified - in production transfer from escrow)
-    msg!("Player {} left table {}", player_key, ctx.accounts.table.table_id);
+    emit!(PlayerLeftEvent {
+        table_id: table.table_id,
+        player: player_key,
+        seat,
+        stack: player.stack,
+    });
+
+    msg!("Player {} left table {}", player_key, table.table_id);
+    Ok(())
+}
+
+/// Hand a freed seat to the first waitlisted player. Depositors take the seat
+/// now, paid back from `wallets` in queue order; anyone else gets a window to
+/// claim it.
+pub fn offer_seat_to_waitlist<'a, 'info: 'a>(
+    table: &mut Table,
+    seat: u8,
+    waitlist: &mut Account<'info, TableWaitlist>,
+    wallets: &mut impl Iterator<Item = &'a AccountInfo<'info>>,
+    now: i64,
+) -> Result<()> {
+    let Some(next) = waitlist.entries.first().copied() else {
+        return Ok(());
+    };
+    if next.deposit > 0 {
+        let wallet = wallets
+            .next()
+            .filter(|w| w.key() == next.player && w.is_writable)
+            .ok_or(PokerError::WaitlistMismatch)?;
+        TableWaitlist::remove_entry(waitlist, 0, wallet)?;
+        let buy_in = table.buy_in;
+        let mut game = table.to_engine();
+        game.seat_player(seat, next.player.to_bytes(), buy_in).map_err(PokerError::from)?;
+        table.apply_engine(&game);
+        table.reset_time_bank(seat);
+        table.set_muck(seat, false);
+        emit!(PlayerJoinedEvent {
+            table_id: table.table_id,
+            player: next.player,
+            seat,
+            stack: buy_in,
+        });
+    } else {
+        waitlist.entries.remove(0);
+        let expires_at = now + Table::RESERVATION_TIMEOUT;
+        table.reserve_seat(seat, next.player, expires_at);
+        emit!(SeatReservedEvent {
+            table_id: table.table_id,
+            player: next.player,
+            seat,
+            expires_at,
+        });
+    }
+    table.waitlist_len = waitlist.entries.len() as u8;
+    Ok(())
+}
+
+fn leave_after_hand(table: &mut Table, seat: u8, player_key: Pubkey) -> Result<()> {
+    require!(!table.is_leaving(seat), PokerError::AlreadyLeaving);
+
+    let can_act = table.get_player(seat).is_some_and(|p| !p.has_folded && !p.is_all_in);
+    let betting = matches!(
+        table.game_state(),
+        GameState::PreFlop | GameState::Flop | GameState::Turn | GameState::River
+    );
+    if betting && can_act {
+        let round = table.round();
+        let mut game = table.to_engine();
+        let outcome = game.force_fold(seat).map_err(PokerError::from)?;
+        table.apply_engine(&game);
+
+        emit!(ActionTakenEvent {
+            table_id: table.table_id,
+            player: player_key,
+            seat,
+            action_type: ActionType::Fold,
+            amount: outcome.amount,
+            new_pot: table.pot,
+            round,
+            time_bank: table.time_bank(seat),
+        });
+
+        if outcome.round_complete {
+            emit!(RoundEndedEvent {
+                table_id: table.table_id,
+                round: table.round(),
+                community_cards: table.community_cards(),
+            });
+        }
+
+        if let Some(uncalled) = outcome.uncalled {
+            emit!(UncalledBetReturnedEvent {
+                table_id: table.table_id,
+                player: table.get_player(uncalled.seat).map(|p| p.pubkey).unwrap_or_default(),
+                seat: uncalled.seat,
+                amount: uncalled.amount,
+            });
+        }
+    }
+
+    table.set_leaving(seat, true);
+    table.last_action_at = Clock::get()?.unix_timestamp;
+
+    msg!("Player {} leaving table {} after this hand", player_key, table.table_id);
     Ok(())
 }
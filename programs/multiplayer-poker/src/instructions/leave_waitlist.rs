@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::PokerError;
+
+#[derive(Accounts)]
+pub struct LeaveWaitlist<'info> {
+    #[account(
+        mut,
+        seeds = [Table::SEED, table.load_current()?.creator.as_ref(), &table.load_current()?.table_id.to_le_bytes()],
+        bump = table.load_current()?.bump
+    )]
+    pub table: AccountLoader<'info, Table>,
+
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [TableWaitlist::SEED, table.key().as_ref()],
+        bump = waitlist.bump,
+        constraint = waitlist.version == TableWaitlist::VERSION @ PokerError::UnsupportedAccountVersion
+    )]
+    pub waitlist: Account<'info, TableWaitlist>,
+}
+
+pub fn handler(ctx: Context<LeaveWaitlist>) -> Result<()> {
+    let mut table = ctx.accounts.table.load_mut()?;
+    let player_key = ctx.accounts.player.key();
+
+    let waitlist = &mut ctx.accounts.waitlist;
+    let index = waitlist.position(&player_key).ok_or(PokerError::NotWaitlisted)?;
+    let entry = TableWaitlist::remove_entry(waitlist, index, &ctx.accounts.player.to_account_info())?;
+    table.waitlist_len = waitlist.entries.len() as u8;
+
+    msg!("Player {} left the table {} waitlist, {} refunded", player_key, table.table_id, entry.deposit);
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_lang::Discriminator;
+use crate::migration::LegacyTable;
+use crate::state::*;
+use crate::error::PokerError;
+
+#[derive(Accounts)]
+pub struct MigrateTable<'info> {
+    /// CHECK: may hold any table layout; the version and PDA are checked in the handler
+    #[account(mut, owner = crate::ID)]
+    pub table: UncheckedAccount<'info>,
+
+    /// Covers any extra rent the new layout needs
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<MigrateTable>) -> Result<()> {
+    let info = ctx.accounts.table.to_account_info();
+    let version = Table::layout_version(&info.try_borrow_data()?)
+        .ok_or(PokerError::UnsupportedAccountVersion)?;
+    if version == Table::VERSION {
+        msg!("Table already at version {}", version);
+        return Ok(());
+    }
+
+    let required = Rent::get()?.minimum_balance(Table::MAX_SIZE);
+    if info.lamports() < required {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: info.clone(),
+                },
+            ),
+            required - info.lamports(),
+        )?;
+    }
+
+    // The legacy layout is the only older one
+    let legacy = LegacyTable::deserialize(&mut &info.try_borrow_data()?[8..])?;
+    let (address, bump) = Pubkey::find_program_address(
+        &[Table::SEED, legacy.creator.as_ref(), &legacy.table_id.to_le_bytes()],
+        &crate::ID,
+    );
+    require_keys_eq!(address, info.key(), ErrorCode::ConstraintSeeds);
+    let table = Table::from_legacy(&legacy, bump)?;
+
+    info.realloc(Table::MAX_SIZE, false)?;
+    let mut data = info.try_borrow_mut_data()?;
+    data[..8].copy_from_slice(&Table::DISCRIMINATOR);
+    data[8..].copy_from_slice(bytemuck::bytes_of(&table));
+
+    emit!(TableMigratedEvent {
+        table_id: table.table_id,
+        from_version: version,
+        to_version: Table::VERSION,
+    });
+
+    msg!("Table {} migrated from version {} to {}", table.table_id, version, Table::VERSION);
+    Ok(())
+}
@@ -1,3 +1,5 @@
+#![allow(ambiguous_glob_reexports)]
+
 pub mod create_table;
 pub mod join_table;
 pub mod leave_table;
@@ -5,6 +7,25 @@ pub mod start_game;
 pub mod action;
 pub mod showdown;
 pub mod distribute_pot;
+pub mod close_table;
+pub mod force_close_table;
+pub mod migrate_table;
+pub mod add_invitee;
+pub mod remove_invitee;
+pub mod join_waitlist;
+pub mod leave_waitlist;
+pub mod reserve_seat;
+pub mod timeout_player;
+pub mod create_session;
+pub mod revoke_session;
+pub mod set_gas_sponsor;
+pub mod close_gas_sponsor;
+pub mod vote_run_twice;
+pub mod rabbit_hunt;
+pub mod set_muck;
+pub mod show_or_muck;
+pub mod set_jackpot;
+pub mod set_table_jackpot;
 
 pub use create_table::*;
 pub use join_table::*;
@@ -13,3 +34,22 @@ pub use start_game::*;
 pub use action::*;
 pub use showdown::*;
 pub use distribute_pot::*;
+pub use close_table::*;
+pub use force_close_table::*;
+pub use migrate_table::*;
+pub use add_invitee::*;
+pub use remove_invitee::*;
+pub use join_waitlist::*;
+pub use leave_waitlist::*;
+pub use reserve_seat::*;
+pub use timeout_player::*;
+pub use create_session::*;
+pub use revoke_session::*;
+pub use set_gas_sponsor::*;
+pub use close_gas_sponsor::*;
+pub use vote_run_twice::*;
+pub use rabbit_hunt::*;
+pub use set_muck::*;
+pub use show_or_muck::*;
+pub use set_jackpot::*;
+pub use set_table_jackpot::*;
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::PokerError;
+
+#[derive(Accounts)]
+pub struct RabbitHunt<'info> {
+    // Permissionless and read-only: the cards are only shown, never played
+    #[account(
+        seeds = [Table::SEED, table.load_current()?.creator.as_ref(), &table.load_current()?.table_id.to_le_bytes()],
+        bump = table.load_current()?.bump
+    )]
+    pub table: AccountLoader<'info, Table>,
+}
+
+pub fn handler(ctx: Context<RabbitHunt>) -> Result<()> {
+    let table = ctx.accounts.table.load()?;
+
+    let game = table.to_engine();
+    let board = game.rabbit_hunt().map_err(PokerError::from)?;
+    let dealt = table.community_cards().iter().flatten().count() as u8;
+    require!(dealt < 5, PokerError::InvalidAction);
+
+    emit!(RabbitHuntEvent {
+        table_id: table.table_id,
+        board: board.map(|c| c.map(Into::into)),
+        dealt,
+    });
+
+    msg!("Rabbit hunt at table {}", table.table_id);
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::PokerError;
+
+#[derive(Accounts)]
+pub struct RemoveInvitee<'info> {
+    #[account(
+        has_one = creator,
+        seeds = [Table::SEED, table.load_current()?.creator.as_ref(), &table.load_current()?.table_id.to_le_bytes()],
+        bump = table.load_current()?.bump
+    )]
+    pub table: AccountLoader<'info, Table>,
+
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [TableAllowlist::SEED, table.key().as_ref()],
+        bump = allowlist.bump,
+        constraint = allowlist.version == TableAllowlist::VERSION @ PokerError::UnsupportedAccountVersion
+    )]
+    pub allowlist: Account<'info, TableAllowlist>,
+}
+
+pub fn handler(ctx: Context<RemoveInvitee>, invitee: Pubkey) -> Result<()> {
+    let table = ctx.accounts.table.load()?;
+    require!(table.game_state() == GameState::Waiting, PokerError::GameInProgress);
+
+    let invitees = &mut ctx.accounts.allowlist.invitees;
+    let index = invitees.iter().position(|k| k == &invitee).ok_or(PokerError::NotInvited)?;
+    invitees.swap_remove(index);
+
+    msg!("Removed {} from table {} allowlist", invitee, table.table_id);
+    Ok(())
+}
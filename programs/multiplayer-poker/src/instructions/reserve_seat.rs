@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use crate::instructions::join_table::check_admission;
+use crate::state::*;
+use crate::error::PokerError;
+
+#[derive(Accounts)]
+pub struct ReserveSeat<'info> {
+    #[account(
+        mut,
+        seeds = [Table::SEED, table.load_current()?.creator.as_ref(), &table.load_current()?.table_id.to_le_bytes()],
+        bump = table.load_current()?.bump
+    )]
+    pub table: AccountLoader<'info, Table>,
+
+    pub player: Signer<'info>,
+
+    #[account(
+        seeds = [TableAllowlist::SEED, table.key().as_ref()],
+        bump = allowlist.bump
+    )]
+    pub allowlist: Option<Account<'info, TableAllowlist>>,
+
+    pub gate_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: verified in `gate::check`
+    pub gate_metadata: Option<UncheckedAccount<'info>>,
+}
+
+pub fn handler(ctx: Context<ReserveSeat>, seat: Option<u8>, password: Option<Vec<u8>>) -> Result<()> {
+    let table_key = ctx.accounts.table.key();
+    let mut table = ctx.accounts.table.load_mut()?;
+    let player_key = ctx.accounts.player.key();
+    let now = Clock::get()?.unix_timestamp;
+
+    check_admission(
+        &table,
+        &table_key,
+        &player_key,
+        password,
+        ctx.accounts.allowlist.as_deref(),
+        ctx.accounts.gate_token_account.as_deref(),
+        ctx.accounts.gate_metadata.as_ref().map(AsRef::as_ref),
+    )?;
+    require!(table.game_state() == GameState::Waiting, PokerError::GameInProgress);
+    require!(table.find_seat(&player_key).is_none(), PokerError::AlreadySeated);
+    // Free seats belong to the waitlist while anyone is queued
+    require!(table.waitlist_len == 0, PokerError::WaitlistAhead);
+
+    let seat = match seat {
+        Some(seat) => seat,
+        None => table.first_free_seat(&player_key, now).ok_or(PokerError::TableFull)?,
+    };
+    require!(seat < table.max_players, PokerError::InvalidSeat);
+    require!(!table.players[seat as usize].is_occupied(), PokerError::SeatOccupied);
+    if let Some(holder) = table.reservation(seat, now) {
+        require_keys_eq!(holder, player_key, PokerError::SeatReserved);
+    }
+
+    // One hold per wallet, and no new one until the last has been over for as
+    // long as it lasted, so nobody can keep a seat by re-reserving it
+    let held = (0..9).any(|other| {
+        table.reserved_by[other] == player_key && table.reserved_until[other] + Table::RESERVATION_TIMEOUT > now
+    });
+    require!(!held, PokerError::ReservationCooldown);
+    let expires_at = now + Table::RESERVATION_TIMEOUT;
+    table.reserve_seat(seat, player_key, expires_at);
+
+    emit!(SeatReservedEvent {
+        table_id: table.table_id,
+        player: player_key,
+        seat,
+        expires_at,
+    });
+
+    msg!("Seat {} at table {} held for {} until {}", seat, table.table_id, player_key, expires_at);
+    Ok(())
+}
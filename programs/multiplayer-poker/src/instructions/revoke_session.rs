@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct RevokeSession<'info> {
+    // Any version, so sessions at tables awaiting migration can still be closed
+    /// CHECK: only used to derive the session address
+    pub table: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        close = player,
+        seeds = [SessionToken::SEED, table.key().as_ref(), player.key().as_ref()],
+        bump = session.bump
+    )]
+    pub session: Account<'info, SessionToken>,
+}
+
+pub fn handler(ctx: Context<RevokeSession>) -> Result<()> {
+    msg!(
+        "Player {} revoked session key {}",
+        ctx.accounts.player.key(),
+        ctx.accounts.session.session_key
+    );
+    Ok(())
+}
@@ -0,0 +1,107 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::sysvar::instructions::{load_current_index_checked, load_instruction_at_checked};
+use crate::state::*;
+use crate::error::PokerError;
+
+#[derive(Accounts)]
+pub struct SetGasSponsor<'info> {
+    #[account(
+        has_one = creator,
+        seeds = [Table::SEED, table.load_current()?.creator.as_ref(), &table.load_current()?.table_id.to_le_bytes()],
+        bump = table.load_current()?.bump
+    )]
+    pub table: AccountLoader<'info, Table>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = GasSponsor::MAX_SIZE,
+        seeds = [GasSponsor::SEED, table.key().as_ref()],
+        bump
+    )]
+    pub sponsor: Account<'info, GasSponsor>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<SetGasSponsor>, relayer: Pubkey, reimbursement: u64, deposit: u64) -> Result<()> {
+    let table = ctx.accounts.table.load()?;
+
+    let sponsor = &mut ctx.accounts.sponsor;
+    if sponsor.table == Pubkey::default() {
+        sponsor.version = GasSponsor::VERSION;
+        sponsor.table = ctx.accounts.table.key();
+        sponsor.bump = ctx.bumps.sponsor;
+    }
+    require!(sponsor.version == GasSponsor::VERSION, PokerError::UnsupportedAccountVersion);
+    sponsor.relayer = relayer;
+    sponsor.reimbursement = reimbursement;
+
+    if deposit > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.creator.to_account_info(),
+                    to: ctx.accounts.sponsor.to_account_info(),
+                },
+            ),
+            deposit,
+        )?;
+    }
+
+    msg!("Table {} sponsors relayer {} at {} lamports per transaction", table.table_id, relayer, reimbursement);
+    Ok(())
+}
+
+/// Pay back the relayer that submitted a gameplay transaction, shared by
+/// `action`, `timeout_player`, `vote_run_twice`, `show_or_muck`, `set_muck`
+/// and the `start_game`, `showdown` and `distribute_pot` cranks. The accounts
+/// are optional, but a sponsor only pays its own relayer, and only once per
+/// transaction however many sponsored instructions it packs.
+pub fn reimburse_relayer<'info>(
+    table_id: u64,
+    sponsor: Option<&Account<'info, GasSponsor>>,
+    relayer: Option<&Signer<'info>>,
+    instructions: Option<&AccountInfo<'info>>,
+) -> Result<()> {
+    let Some(sponsor) = sponsor else { return Ok(()) };
+    let relayer = relayer.ok_or(PokerError::InvalidRelayer)?;
+    let instructions = instructions.ok_or(PokerError::InstructionsSysvarRequired)?;
+    if !first_sponsored_instruction(&sponsor.key(), instructions)? {
+        return Ok(());
+    }
+    let amount = GasSponsor::reimburse(sponsor, relayer)?;
+    if amount > 0 {
+        emit!(RelayerReimbursedEvent {
+            table_id,
+            relayer: relayer.key(),
+            amount,
+        });
+    }
+    Ok(())
+}
+
+/// Whether the running instruction is the transaction's first top-level call
+/// to this program that passes `sponsor`. A call made through another
+/// program's CPI never is, so wrapping calls in one instruction earns nothing.
+fn first_sponsored_instruction(sponsor: &Pubkey, instructions: &AccountInfo) -> Result<bool> {
+    let uses_sponsor = |ix: &Instruction| {
+        ix.program_id == crate::ID && ix.accounts.iter().any(|meta| meta.pubkey == *sponsor)
+    };
+    let current = load_current_index_checked(instructions)? as usize;
+    if !uses_sponsor(&load_instruction_at_checked(current, instructions)?) {
+        return Ok(false);
+    }
+    for index in 0..current {
+        if uses_sponsor(&load_instruction_at_checked(index, instructions)?) {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+use crate::state::*;
+use crate::error::PokerError;
+
+#[derive(Accounts)]
+pub struct SetJackpot<'info> {
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = Jackpot::MAX_SIZE,
+        seeds = [Jackpot::SEED],
+        bump
+    )]
+    pub jackpot: Account<'info, Jackpot>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// The program's own ProgramData, whose upgrade authority acts as admin
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = bpf_loader_upgradeable::ID,
+        constraint = program_data.upgrade_authority_address == Some(admin.key()) @ PokerError::Unauthorized
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<SetJackpot>, drop_bps: u16, loser_bps: u16, winner_bps: u16, table_bps: u16) -> Result<()> {
+    Jackpot::validate(drop_bps, loser_bps, winner_bps, table_bps)?;
+
+    let jackpot = &mut ctx.accounts.jackpot;
+    if jackpot.version == 0 {
+        jackpot.version = Jackpot::VERSION;
+        jackpot.bump = ctx.bumps.jackpot;
+    }
+    require!(jackpot.version == Jackpot::VERSION, PokerError::UnsupportedAccountVersion);
+    jackpot.drop_bps = drop_bps;
+    jackpot.loser_bps = loser_bps;
+    jackpot.winner_bps = winner_bps;
+    jackpot.table_bps = table_bps;
+
+    msg!("Jackpot drop set to {} bps with a balance of {}", drop_bps, jackpot.balance);
+    Ok(())
+}
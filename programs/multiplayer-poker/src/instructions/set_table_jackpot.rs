@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::PokerError;
+
+#[derive(Accounts)]
+pub struct SetTableJackpot<'info> {
+    #[account(
+        mut,
+        has_one = creator,
+        seeds = [Table::SEED, table.load_current()?.creator.as_ref(), &table.load_current()?.table_id.to_le_bytes()],
+        bump = table.load_current()?.bump
+    )]
+    pub table: AccountLoader<'info, Table>,
+
+    pub creator: Signer<'info>,
+
+    // Must exist, so `distribute_pot` always has a jackpot to pay into
+    #[account(seeds = [Jackpot::SEED], bump = jackpot.bump)]
+    pub jackpot: Account<'info, Jackpot>,
+}
+
+pub fn handler(ctx: Context<SetTableJackpot>, enabled: bool) -> Result<()> {
+    let mut table = ctx.accounts.table.load_mut()?;
+    require!(table.game_state() == GameState::Waiting, PokerError::GameInProgress);
+
+    table.jackpot = enabled as u8;
+
+    msg!("Table {} {} the jackpot", table.table_id, if enabled { "joined" } else { "left" });
+    Ok(())
+}
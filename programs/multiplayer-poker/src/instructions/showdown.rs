@@ -1,41 +1,83 @@
 use anchor_lang::prelude::*;
 use crate::state::*;
 use crate::error::PokerError;
+use crate::instructions::set_gas_sponsor::reimburse_relayer;
 
 #[derive(Accounts)]
 pub struct Showdown<'info> {
+    // Permissionless: the outcome depends only on the table state
+    #[account(
+        mut,
+        seeds = [Table::SEED, table.load_current()?.creator.as_ref(), &table.load_current()?.table_id.to_le_bytes()],
+        bump = table.load_current()?.bump
+    )]
+    pub table: AccountLoader<'info, Table>,
+
+    /// Fee payer submitting the crank, paid back by `sponsor`
     #[account(mut)]
-    pub table: Account<'info, Table>,
-    
-    pub authority: Signer<'info>,
+    pub relayer: Option<Signer<'info>>,
+
+    #[account(
+        mut,
+        seeds = [GasSponsor::SEED, table.key().as_ref()],
+        bump = sponsor.bump
+    )]
+    pub sponsor: Option<Account<'info, GasSponsor>>,
+
+    /// CHECK: the instructions sysvar, required with `sponsor`
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: Option<UncheckedAccount<'info>>,
 }
 
 pub fn handler(ctx: Context<Showdown>) -> Result<()> {
-    let table = &mut ctx.accounts.table;
+    let mut table = ctx.accounts.table.load_mut()?;
 
-    require!(table.game_state == GameState::Showdown, PokerError::InvalidGameState);
+    // Evaluate every remaining hand; winners are paid in distribute_pot
+    let mut game = table.to_engine();
+    let result = game.showdown().map_err(PokerError::from)?;
+    table.apply_engine(&game);
 
-    // Reveal all active players' hole cards
-    for (seat, player) in table.get_active_players() {
-        if let Some(player_mut) = table.get_player_mut(seat) {
-            // In production, verify commitment before revealing
-            // For now, cards are already set during deal
-            
+    // Reveal in showdown order; winners always show, beaten hands may muck
+    let mut decisions = Vec::new();
+    for seat in result.order.iter().flatten().copied() {
+        let Some(player) = table.get_player(seat) else { continue };
+        let shown = result.shown[seat as usize];
+        decisions.push(ShowdownDecision { seat, player: player.pubkey, shown });
+        if !shown {
+            continue;
+        }
+        // In production, verify commitment before revealing
+        if let [Some(first), Some(second)] = player.hole_cards {
             emit!(CardRevealedEvent {
                 table_id: table.table_id,
-                player: player_mut.pubkey,
+                player: player.pubkey,
                 seat,
-                cards: [
-                    player_mut.hole_cards[0].unwrap(),
-                    player_mut.hole_cards[1].unwrap(),
-                ],
+                cards: [first, second],
             });
         }
     }
 
-    // Determine winners (simplified - in production implement proper hand evaluation)
-    // For now, we'll just mark for distribution
-    table.game_state = GameState::Finished;
+    if let Some(second_board) = table.second_board() {
+        emit!(BoardRunTwiceEvent {
+            table_id: table.table_id,
+            first_board: table.community_cards(),
+            second_board,
+        });
+    }
+
+    emit!(HandHistoryEvent {
+        table_id: table.table_id,
+        hand_number: table.hand_number,
+        community_cards: table.community_cards(),
+        showdown: decisions,
+    });
+
+    reimburse_relayer(
+        table.table_id,
+        ctx.accounts.sponsor.as_ref(),
+        ctx.accounts.relayer.as_ref(),
+        ctx.accounts.instructions.as_deref(),
+    )?;
 
     msg!("Showdown completed at table {}", table.table_id);
     Ok(())
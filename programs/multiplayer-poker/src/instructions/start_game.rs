@@ -2,94 +2,73 @@ use anchor_lang::prelude::*;
 use crate::state::*;
 use crate::error::PokerError;
 use anchor_lang::solana_program::keccak;
+use crate::instructions::set_gas_sponsor::reimburse_relayer;
 
 #[derive(Accounts)]
 pub struct StartGame<'info> {
-    #[account(mut)]
-    pub table: Account<'info, Table>,
+    #[account(
+        mut,
+        seeds = [Table::SEED, table.load_current()?.creator.as_ref(), &table.load_current()?.table_id.to_le_bytes()],
+        bump = table.load_current()?.bump
+    )]
+    pub table: AccountLoader<'info, Table>,
     
+    /// The creator or a seated player
+    #[account(
+        constraint = authority.key() == table.load_current()?.creator
+            || table.load_current()?.find_seat(&authority.key()).is_some() @ PokerError::Unauthorized
+    )]
     pub authority: Signer<'info>,
+
+    /// Fee payer submitting the crank, paid back by `sponsor`
+    #[account(mut)]
+    pub relayer: Option<Signer<'info>>,
+
+    #[account(
+        mut,
+        seeds = [GasSponsor::SEED, table.key().as_ref()],
+        bump = sponsor.bump
+    )]
+    pub sponsor: Option<Account<'info, GasSponsor>>,
+
+    /// CHECK: the instructions sysvar, required with `sponsor`
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: Option<UncheckedAccount<'info>>,
 }
 
 pub fn handler(ctx: Context<StartGame>) -> Result<()> {
-    let table = &mut ctx.accounts.table;
+    let mut table = ctx.accounts.table.load_mut()?;
     let clock = Clock::get()?;
 
-    require!(table.game_state == GameState::Waiting, PokerError::InvalidGameState);
-    require!(table.player_count >= 2, PokerError::NotEnoughPlayers);
-
-    // Set dealer button (rotate from previous or start at 0)
-    let active_players: Vec<u8> = table.get_active_players().iter().map(|(s, _)| *s).collect();
-    table.dealer_position = active_players[0];
-    
-    // Set small blind and big blind positions
-    let dealer_idx = active_players.iter().position(|&s| s == table.dealer_position).unwrap();
-    table.small_blind_position = active_players[(dealer_idx + 1) % active_players.len()];
-    table.big_blind_position = active_players[(dealer_idx + 2) % active_players.len()];
-
-    // Post blinds
-    if let Some(sb_player) = table.get_player_mut(table.small_blind_position) {
-        let sb_amount = sb_player.stack.min(table.small_blind);
-        sb_player.stack = sb_player.stack.checked_sub(sb_amount).ok_or(PokerError::MathOverflow)?;
-        sb_player.bet = sb_amount;
-        if sb_player.stack == 0 {
-            sb_player.is_all_in = true;
-        }
-    }
-
-    if let Some(bb_player) = table.get_player_mut(table.big_blind_position) {
-        let bb_amount = bb_player.stack.min(table.big_blind);
-        bb_player.stack = bb_player.stack.checked_sub(bb_amount).ok_or(PokerError::MathOverflow)?;
-        bb_player.bet = bb_amount;
-        if bb_player.stack == 0 {
-            bb_player.is_all_in = true;
-        }
-    }
-
-    table.pot = table.small_blind + table.big_blind;
-    table.last_raise_amount = table.big_blind;
-    table.min_raise = table.big_blind;
-
-    // Deal hole cards (simplified randomness using clock + table state)
-    // In production, use commitment scheme or VRF
+    // Deck seed from the clock and table id. Not secure: validators can pick
+    // the slot, and the seed is stored in the table, so anyone can recompute
+    // every card of the hand. Real money needs a VRF or a commit-reveal deal.
     let mut seed = clock.unix_timestamp.to_le_bytes().to_vec();
     seed.extend_from_slice(&table.table_id.to_le_bytes());
     seed.extend_from_slice(&clock.slot.to_le_bytes());
+    let deck_seed = keccak::hash(&seed).to_bytes();
 
-    let mut deck = generate_deck();
-    shuffle_deck(&mut deck, &seed);
+    // Rotate the button, post blinds and deal hole cards
+    let mut game = table.to_engine();
+    let uncalled = game.start_hand(deck_seed).map_err(PokerError::from)?;
+    table.apply_engine(&game);
+    table.preflop_stats = [0; 9];
 
-    // Deal cards to each active player
-    let mut card_idx = 0;
-    for (seat, _) in table.get_active_players() {
-        if let Some(player) = table.get_player_mut(seat) {
-            if card_idx + 1 < deck.len() {
-                player.hole_cards[0] = Some(deck[card_idx]);
-                player.hole_cards[1] = Some(deck[card_idx + 1]);
-                // Create commitment hash (in production, use proper commitment)
-                let mut commit_data = player.hole_cards[0].unwrap().to_u8().to_le_bytes().to_vec();
-                commit_data.extend_from_slice(&player.hole_cards[1].unwrap().to_u8().to_le_bytes());
-                commit_data.extend_from_slice(&seed);
-                let hash = keccak::hash(&commit_data);
-                player.hole_cards_committed = hash.to_bytes();
-                card_idx += 2;
-            }
-        }
+    if table.hand_number % Table::TIME_BANK_HANDS == 0 {
+        table.time_bank_used = [0; 9];
     }
 
-    // Set first player to act (after big blind)
-    table.current_player = table.get_next_active_player(table.big_blind_position);
-    table.game_state = GameState::PreFlop;
-    table.round = Round::PreFlop;
-    table.last_action_at = clock.unix_timestamp;
-
-    // Reset all players' acted status
-    for player_opt in &mut table.players {
-        if let Some(player) = player_opt {
-            player.has_acted = false;
+    // Create commitment hash for each dealt hand (in production, use proper commitment)
+    for seat in table.players.iter_mut().filter(|s| s.is_occupied()) {
+        if let [Some(first), Some(second)] = seat.hole_cards() {
+            let mut commit_data = vec![first.to_u8(), second.to_u8()];
+            commit_data.extend_from_slice(&deck_seed);
+            seat.hole_cards_committed = keccak::hash(&commit_data).to_bytes();
         }
     }
 
+    table.last_action_at = clock.unix_timestamp;
+
     emit!(GameStartedEvent {
         table_id: table.table_id,
         dealer_position: table.dealer_position,
@@ -97,34 +76,22 @@ pub fn handler(ctx: Context<StartGame>) -> Result<()> {
         big_blind_position: table.big_blind_position,
     });
 
-    msg!("Game started at table {}", table.table_id);
-    Ok(())
-}
-
-fn generate_deck() -> Vec<Card> {
-    let mut deck = Vec::new();
-    for suit in [Suit::Clubs, Suit::Diamonds, Suit::Hearts, Suit::Spades] {
-        for rank in [
-            Rank::Two, Rank::Three, Rank::Four, Rank::Five, Rank::Six,
-            Rank::Seven, Rank::Eight, Rank::Nine, Rank::Ten,
-            Rank::Jack, Rank::Queen, Rank::King, Rank::Ace,
-        ] {
-            deck.push(Card::new(suit, rank));
-        }
+    if let Some(uncalled) = uncalled {
+        emit!(UncalledBetReturnedEvent {
+            table_id: table.table_id,
+            player: table.get_player(uncalled.seat).map(|p| p.pubkey).unwrap_or_default(),
+            seat: uncalled.seat,
+            amount: uncalled.amount,
+        });
     }
-    deck
-}
 
-fn shuffle_deck(deck: &mut Vec<Card>, seed: &[u8]) {
-    // Simple Fisher-Yates shuffle using seed for randomness
-    let mut rng_seed = 0u64;
-    for (i, &byte) in seed.iter().enumerate() {
-        rng_seed ^= (byte as u64) << (i % 8 * 8);
-    }
+    reimburse_relayer(
+        table.table_id,
+        ctx.accounts.sponsor.as_ref(),
+        ctx.accounts.relayer.as_ref(),
+        ctx.accounts.instructions.as_deref(),
+    )?;
 
-    for i in (1..deck.len()).rev() {
-        rng_seed = rng_seed.wrapping_mul(1103515245).wrapping_add(12345);
-        let j = (rng_seed as usize) % (i + 1);
-        deck.swap(i, j);
-    }
+    msg!("Game started at table {}", table.table_id);
+    Ok(())
 }
@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::PokerError;
+use crate::instructions::set_gas_sponsor::reimburse_relayer;
+
+#[derive(Accounts)]
+pub struct TimeoutPlayer<'info> {
+    // Permissionless: anyone may move the game on once the clock runs out
+    #[account(
+        mut,
+        seeds = [Table::SEED, table.load_current()?.creator.as_ref(), &table.load_current()?.table_id.to_le_bytes()],
+        bump = table.load_current()?.bump
+    )]
+    pub table: AccountLoader<'info, Table>,
+
+    /// Fee payer submitting for the player, paid back by `sponsor`
+    #[account(mut)]
+    pub relayer: Option<Signer<'info>>,
+
+    #[account(
+        mut,
+        seeds = [GasSponsor::SEED, table.key().as_ref()],
+        bump = sponsor.bump
+    )]
+    pub sponsor: Option<Account<'info, GasSponsor>>,
+
+    /// CHECK: the instructions sysvar, required with `sponsor`
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: Option<UncheckedAccount<'info>>,
+}
+
+pub fn handler(ctx: Context<TimeoutPlayer>) -> Result<()> {
+    let mut table = ctx.accounts.table.load_mut()?;
+    let now = Clock::get()?.unix_timestamp;
+
+    let seat = table.current_player().ok_or(PokerError::InvalidGameState)?;
+    let player = table.get_player(seat).ok_or(PokerError::PlayerNotFound)?;
+    let deadline = table.last_action_at + table.action_timeout + table.time_bank(seat) as i64;
+    require!(now > deadline, PokerError::ActionNotTimedOut);
+
+    // Check when there is nothing to call, fold otherwise
+    let round = table.round();
+    let mut game = table.to_engine();
+    let action_type = if player.bet < game.current_bet() { ActionType::Fold } else { ActionType::Check };
+    let outcome = game.act(seat, action_type.into(), None).map_err(PokerError::from)?;
+    table.apply_engine(&game);
+
+    table.time_bank_used[seat as usize] = Table::TIME_BANK;
+    table.last_action_at = now;
+
+    emit!(ActionTakenEvent {
+        table_id: table.table_id,
+        player: player.pubkey,
+        seat,
+        action_type,
+        amount: outcome.amount,
+        new_pot: table.pot,
+        round,
+        time_bank: 0,
+    });
+
+    if outcome.round_complete {
+        emit!(RoundEndedEvent {
+            table_id: table.table_id,
+            round: table.round(),
+            community_cards: table.community_cards(),
+        });
+    }
+
+    if let Some(uncalled) = outcome.uncalled {
+        emit!(UncalledBetReturnedEvent {
+            table_id: table.table_id,
+            player: table.get_player(uncalled.seat).map(|p| p.pubkey).unwrap_or_default(),
+            seat: uncalled.seat,
+            amount: uncalled.amount,
+        });
+    }
+
+    reimburse_relayer(
+        table.table_id,
+        ctx.accounts.sponsor.as_ref(),
+        ctx.accounts.relayer.as_ref(),
+        ctx.accounts.instructions.as_deref(),
+    )?;
+
+    msg!("Player {} timed out at table {}", player.pubkey, table.table_id);
+    Ok(())
+}
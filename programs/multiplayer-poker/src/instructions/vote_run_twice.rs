@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::PokerError;
+use crate::instructions::set_gas_sponsor::reimburse_relayer;
+
+#[derive(Accounts)]
+pub struct VoteRunTwice<'info> {
+    #[account(
+        mut,
+        seeds = [Table::SEED, table.load_current()?.creator.as_ref(), &table.load_current()?.table_id.to_le_bytes()],
+        bump = table.load_current()?.bump
+    )]
+    pub table: AccountLoader<'info, Table>,
+
+    /// The player's wallet, or a session key they authorized
+    pub signer: Signer<'info>,
+
+    /// CHECK: the player's wallet; `signer` must be it or its session key
+    pub player: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [SessionToken::SEED, table.key().as_ref(), player.key().as_ref()],
+        bump = session.bump
+    )]
+    pub session: Option<Account<'info, SessionToken>>,
+
+    /// Fee payer submitting for the player, paid back by `sponsor`
+    #[account(mut)]
+    pub relayer: Option<Signer<'info>>,
+
+    #[account(
+        mut,
+        seeds = [GasSponsor::SEED, table.key().as_ref()],
+        bump = sponsor.bump
+    )]
+    pub sponsor: Option<Account<'info, GasSponsor>>,
+
+    /// CHECK: the instructions sysvar, required with `sponsor`
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: Option<UncheckedAccount<'info>>,
+}
+
+pub fn handler(ctx: Context<VoteRunTwice>, agree: bool) -> Result<()> {
+    let mut table = ctx.accounts.table.load_mut()?;
+    let player_key = ctx.accounts.player.key();
+    SessionToken::authorize(
+        ctx.accounts.session.as_deref(),
+        &player_key,
+        &ctx.accounts.signer.key(),
+        Clock::get()?.unix_timestamp,
+    )?;
+    let seat = table.find_seat(&player_key).ok_or(PokerError::PlayerNotFound)?;
+
+    let mut game = table.to_engine();
+    game.vote_run_twice(seat, agree).map_err(PokerError::from)?;
+    table.apply_engine(&game);
+
+    emit!(RunTwiceVoteEvent {
+        table_id: table.table_id,
+        player: player_key,
+        seat,
+        agree,
+    });
+
+    reimburse_relayer(
+        table.table_id,
+        ctx.accounts.sponsor.as_ref(),
+        ctx.accounts.relayer.as_ref(),
+        ctx.accounts.instructions.as_deref(),
+    )?;
+
+    msg!("Player {} voted {} on running it twice at table {}", player_key, agree, table.table_id);
+    Ok(())
+}
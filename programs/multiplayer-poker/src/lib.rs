@@ -1,7 +1,10 @@
 use anchor_lang::prelude::*;
 
+pub mod engine;
 pub mod error;
+pub mod gate;
 pub mod instructions;
+pub mod migration;
 pub mod state;
 
 use instructions::*;
@@ -14,6 +17,7 @@ pub mod multiplayer_poker {
     use super::*;
 
     /// Create a new poker table
+    #[allow(clippy::too_many_arguments)]
     pub fn create_table(
         ctx: Context<CreateTable>,
         table_id: u64,
@@ -21,37 +25,152 @@ pub mod multiplayer_poker {
         big_blind: u64,
         max_players: u8,
         buy_in: u64,
+        access: TableAccess,
+        gate: Option<TokenGate>,
     ) -> Result<()> {
-        instructions::create_table::handler(ctx, table_id, small_blind, big_blind, max_players, buy_in)
+        instructions::create_table::handler(ctx, table_id, small_blind, big_blind, max_players, buy_in, access, gate)
     }
 
-    /// Join a poker table
-    pub fn join_table(ctx: Context<JoinTable>, seat: u8) -> Result<()> {
-        instructions::join_table::handler(ctx, seat)
+    /// Join a poker table at `seat`, or the first free one if `None`. `password` is only
+    /// checked on password tables, and token-gated tables need the player's token
+    /// account (plus NFT metadata for collections)
+    pub fn join_table(ctx: Context<JoinTable>, seat: Option<u8>, password: Option<Vec<u8>>) -> Result<()> {
+        instructions::join_table::handler(ctx, seat, password)
     }
 
-    /// Leave a poker table (refund if possible)
+    /// Leave a poker table (refund if possible); while players are waitlisted,
+    /// the freed seat goes to the first of them
     pub fn leave_table(ctx: Context<LeaveTable>) -> Result<()> {
         instructions::leave_table::handler(ctx)
     }
 
-    /// Start a new game (deal cards, set dealer button)
+    /// Start a new game (deal cards, set dealer button); creator or seated players only
     pub fn start_game(ctx: Context<StartGame>) -> Result<()> {
         instructions::start_game::handler(ctx)
     }
 
-    /// Player action (fold, check, call, raise, all-in)
+    /// Player action (fold, check, call, raise, all-in), signed by the player's
+    /// wallet or a session key from `create_session`
     pub fn action(ctx: Context<Action>, action_type: ActionType, amount: Option<u64>) -> Result<()> {
         instructions::action::handler(ctx, action_type, amount)
     }
 
-    /// Showdown - reveal cards and determine winners
+    /// Check or fold for the player to act once their timeout and time bank
+    /// have run out (anyone may call)
+    pub fn timeout_player(ctx: Context<TimeoutPlayer>) -> Result<()> {
+        instructions::timeout_player::handler(ctx)
+    }
+
+    /// Agree (or take back agreement) to run the board twice if the hand goes
+    /// all-in before the river; it only happens when every player left agrees
+    pub fn vote_run_twice(ctx: Context<VoteRunTwice>, agree: bool) -> Result<()> {
+        instructions::vote_run_twice::handler(ctx, agree)
+    }
+
+    /// Showdown - reveal cards in showdown order and determine winners (anyone may call)
     pub fn showdown(ctx: Context<Showdown>) -> Result<()> {
         instructions::showdown::handler(ctx)
     }
 
-    /// Distribute pot to winners
-    pub fn distribute_pot(ctx: Context<DistributePot>) -> Result<()> {
+    /// Show the community cards a finished hand never reached, before the pot
+    /// is distributed (anyone may call)
+    pub fn rabbit_hunt(ctx: Context<RabbitHunt>) -> Result<()> {
+        instructions::rabbit_hunt::handler(ctx)
+    }
+
+    /// Choose whether to muck instead of showing losing hands at showdown.
+    /// Mucking is cosmetic: it only keeps the cards out of showdown events.
+    /// Hole cards sit in plaintext in the table account until `distribute_pot`
+    /// clears them, so they can be read (and stay in account history) anyway
+    pub fn set_muck(ctx: Context<SetMuck>, muck: bool) -> Result<()> {
+        instructions::set_muck::handler(ctx, muck)
+    }
+
+    /// Decide whether to show or muck this hand if it can't win at showdown,
+    /// overriding `set_muck`. Like `set_muck`, this only affects what
+    /// showdown reveals, not what the table account holds
+    pub fn show_or_muck(ctx: Context<ShowOrMuck>, show: bool) -> Result<()> {
+        instructions::show_or_muck::handler(ctx, show)
+    }
+
+    /// Distribute pot to winners and update the profile of every player dealt in
+    /// (passed as remaining accounts in seat order); anyone may call. Jackpot
+    /// tables also pay their drop into the jackpot and pay out bad beats
+    pub fn distribute_pot<'info>(ctx: Context<'_, '_, 'info, 'info, DistributePot<'info>>) -> Result<()> {
         instructions::distribute_pot::handler(ctx)
     }
+
+    /// Close an empty table and return its rent to the creator
+    pub fn close_table(ctx: Context<CloseTable>) -> Result<()> {
+        instructions::close_table::handler(ctx)
+    }
+
+    /// Close an abandoned table (program upgrade authority only)
+    pub fn force_close_table<'info>(ctx: Context<'_, '_, 'info, 'info, ForceCloseTable<'info>>) -> Result<()> {
+        instructions::force_close_table::handler(ctx)
+    }
+
+    /// Invite a wallet to an allowlist table while it is waiting (creator only)
+    pub fn add_invitee(ctx: Context<AddInvitee>, invitee: Pubkey) -> Result<()> {
+        instructions::add_invitee::handler(ctx, invitee)
+    }
+
+    /// Withdraw an invitation while the table is waiting; seated players stay (creator only)
+    pub fn remove_invitee(ctx: Context<RemoveInvitee>, invitee: Pubkey) -> Result<()> {
+        instructions::remove_invitee::handler(ctx, invitee)
+    }
+
+    /// Hold `seat` (or the first free one) for a short while before joining.
+    /// A wallet can't hold another seat until its last hold has cooled down
+    pub fn reserve_seat(ctx: Context<ReserveSeat>, seat: Option<u8>, password: Option<Vec<u8>>) -> Result<()> {
+        instructions::reserve_seat::handler(ctx, seat, password)
+    }
+
+    /// Queue for a seat at a full table, optionally depositing the buy-in to be
+    /// seated as soon as one frees up
+    pub fn join_waitlist(ctx: Context<JoinWaitlist>, deposit: bool, password: Option<Vec<u8>>) -> Result<()> {
+        instructions::join_waitlist::handler(ctx, deposit, password)
+    }
+
+    /// Leave the waitlist and take back any deposit
+    pub fn leave_waitlist(ctx: Context<LeaveWaitlist>) -> Result<()> {
+        instructions::leave_waitlist::handler(ctx)
+    }
+
+    /// Let `session_key` sign `action` for the player at this table until `expires_at`
+    pub fn create_session(ctx: Context<CreateSession>, session_key: Pubkey, expires_at: i64) -> Result<()> {
+        instructions::create_session::handler(ctx, session_key, expires_at)
+    }
+
+    /// End a session early and reclaim its rent
+    pub fn revoke_session(ctx: Context<RevokeSession>) -> Result<()> {
+        instructions::revoke_session::handler(ctx)
+    }
+
+    /// Create or update the table's gas sponsor, which pays `relayer` back
+    /// `reimbursement` lamports per sponsored transaction (creator only)
+    pub fn set_gas_sponsor(ctx: Context<SetGasSponsor>, relayer: Pubkey, reimbursement: u64, deposit: u64) -> Result<()> {
+        instructions::set_gas_sponsor::handler(ctx, relayer, reimbursement, deposit)
+    }
+
+    /// Close the gas sponsor and return its balance to the creator
+    pub fn close_gas_sponsor(ctx: Context<CloseGasSponsor>) -> Result<()> {
+        instructions::close_gas_sponsor::handler(ctx)
+    }
+
+    /// Create or configure the program-wide bad-beat jackpot: the drop taken
+    /// from each pot and how a payout is shared (program upgrade authority only)
+    pub fn set_jackpot(ctx: Context<SetJackpot>, drop_bps: u16, loser_bps: u16, winner_bps: u16, table_bps: u16) -> Result<()> {
+        instructions::set_jackpot::handler(ctx, drop_bps, loser_bps, winner_bps, table_bps)
+    }
+
+    /// Opt the table in or out of the bad-beat jackpot while it is waiting (creator only)
+    pub fn set_table_jackpot(ctx: Context<SetTableJackpot>, enabled: bool) -> Result<()> {
+        instructions::set_table_jackpot::handler(ctx, enabled)
+    }
+
+    /// Rewrite a table stored in an older layout to the current one (anyone may call)
+    pub fn migrate_table(ctx: Context<MigrateTable>) -> Result<()> {
+        instructions::migrate_table::handler(ctx)
+    }
 }
@@ -0,0 +1,125 @@
+//! Older `Table` layouts and their conversion to the current one.
+//!
+//! Each layout this program can still read gets a version number and a
+//! decoder here; `migrate_table` rewrites such accounts in place.
+use anchor_lang::prelude::*;
+
+use crate::error::PokerError;
+use crate::state::*;
+
+/// The original Borsh layout, from before versioning and zero-copy
+pub const LEGACY_TABLE_VERSION: u8 = 0;
+
+/// Every legacy table was allocated with this size
+pub const LEGACY_TABLE_SIZE: usize = 8 + // discriminator
+    32 + // creator
+    8 + // table_id
+    8 + // small_blind
+    8 + // big_blind
+    1 + // max_players
+    8 + // buy_in
+    1 + // game_state
+    1 + // dealer_position
+    1 + // small_blind_position
+    1 + // big_blind_position
+    1 + 32 + // current_player (over-allocated Option<u8>)
+    8 + // pot
+    (1 + 2) * 5 + // community_cards [Option<Card>; 5]
+    1 + // round
+    8 + // last_raise_amount
+    8 + // min_raise
+    (1 + 92) * 9 + // players [Option<Player>; 9]
+    1 + // player_count
+    8 + // created_at
+    8 + // last_action_at
+    8; // action_timeout
+
+/// Table state as stored by the legacy layout
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct LegacyTable {
+    pub creator: Pubkey,
+    pub table_id: u64,
+    pub small_blind: u64,
+    pub big_blind: u64,
+    pub max_players: u8,
+    pub buy_in: u64,
+    pub game_state: GameState,
+    pub dealer_position: u8,
+    pub small_blind_position: u8,
+    pub big_blind_position: u8,
+    pub current_player: Option<u8>,
+    pub pot: u64,
+    pub community_cards: [Option<Card>; 5],
+    pub round: Round,
+    pub last_raise_amount: u64,
+    pub min_raise: u64,
+    pub players: [Option<Player>; 9],
+    pub player_count: u8,
+    pub created_at: i64,
+    pub last_action_at: i64,
+    pub action_timeout: i64,
+}
+
+impl Table {
+    /// Convert a legacy table, voiding any hand in progress.
+    ///
+    /// Current bets go back to their players. The rest of the pot (bets from
+    /// earlier rounds, which the legacy layout does not attribute) is split
+    /// evenly between players who had not folded, odd chips to the lowest seat.
+    pub fn from_legacy(legacy: &LegacyTable, bump: u8) -> Result<Table> {
+        let mut table = Table {
+            creator: legacy.creator,
+            table_id: legacy.table_id,
+            small_blind: legacy.small_blind,
+            big_blind: legacy.big_blind,
+            buy_in: legacy.buy_in,
+            min_raise: legacy.big_blind,
+            created_at: legacy.created_at,
+            last_action_at: legacy.last_action_at,
+            action_timeout: legacy.action_timeout,
+            max_players: legacy.max_players,
+            dealer_position: legacy.dealer_position,
+            bump,
+            ..Table::default()
+        };
+
+        let seated: Vec<Player> = legacy.players.iter().flatten().copied().collect();
+        let bets = seated
+            .iter()
+            .try_fold(0u64, |acc, p| acc.checked_add(p.bet))
+            .ok_or(PokerError::MathOverflow)?;
+        let unattributed = legacy.pot.saturating_sub(bets);
+
+        // Seats sharing the unattributed pot; everyone seated if all had folded
+        let mut live: Vec<u8> = seated.iter().filter(|p| !p.has_folded).map(|p| p.seat).collect();
+        if live.is_empty() {
+            live = seated.iter().map(|p| p.seat).collect();
+        }
+        let share = unattributed / (live.len().max(1) as u64);
+        let odd_chips = unattributed - share * live.len() as u64;
+
+        for player in &seated {
+            let mut stack = player.stack.checked_add(player.bet).ok_or(PokerError::MathOverflow)?;
+            if live.contains(&player.seat) {
+                stack = stack.checked_add(share).ok_or(PokerError::MathOverflow)?;
+            }
+            if live.first() == Some(&player.seat) {
+                stack = stack.checked_add(odd_chips).ok_or(PokerError::MathOverflow)?;
+            }
+            let refunded = Player {
+                stack,
+                bet: 0,
+                hole_cards: [None; 2],
+                hole_cards_committed: [0; 32],
+                is_all_in: false,
+                has_folded: false,
+                has_acted: false,
+                last_action: None,
+                ..*player
+            };
+            table.set_player(player.seat, Some(&refunded));
+        }
+        table.player_count = seated.len() as u8;
+        Ok(table)
+    }
+}
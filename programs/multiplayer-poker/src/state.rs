@@ -1,147 +1,498 @@
 use anchor_lang::prelude::*;
+use crate::error::PokerError;
+use anchor_lang::solana_program::entrypoint::MAX_PERMITTED_DATA_INCREASE;
+use bytemuck::Zeroable;
+use anchor_lang::Discriminator;
+use anchor_lang::solana_program::hash::hashv;
 
-/// Poker table state
-#[account]
+/// Stored in place of an absent card, seat or action in zero-copy accounts
+pub const NONE: u8 = u8::MAX;
+
+/// Poker table state.
+///
+/// Zero-copy: enums and options are stored as `u8`s (`NONE` when absent) and
+/// read through the typed accessors below. Fields are ordered so `repr(C)`
+/// needs no implicit padding.
+#[account(zero_copy)]
 pub struct Table {
+    pub version: u8, // Always the first byte after the discriminator
+    pub access: u8,
+    pub _reserved: [u8; 6],
     pub creator: Pubkey,
+    pub deck_seed: [u8; 32],
     pub table_id: u64,
     pub small_blind: u64,
     pub big_blind: u64,
-    pub max_players: u8,
     pub buy_in: u64,
-    pub game_state: GameState,
-    pub dealer_position: u8,
-    pub small_blind_position: u8,
-    pub big_blind_position: u8,
-    pub current_player: Option<u8>,
     pub pot: u64,
-    pub community_cards: [Option<Card>; 5],
-    pub round: Round,
     pub last_raise_amount: u64,
     pub min_raise: u64,
-    pub players: [Option<Player>; 9],
-    pub player_count: u8,
     pub created_at: i64,
     pub last_action_at: i64,
     pub action_timeout: i64,
+    pub hand_number: u64,
+    pub players: [Seat; 9],
+    pub max_players: u8,
+    pub game_state: u8,
+    pub dealer_position: u8,
+    pub small_blind_position: u8,
+    pub big_blind_position: u8,
+    pub current_player: u8,
+    pub community_cards: [u8; 5],
+    pub round: u8,
+    pub player_count: u8,
+    pub deck_position: u8,
+    pub bump: u8,
+    pub gate: u8,
+    pub password_hash: [u8; 32],
+    pub gate_mint: Pubkey, // Mint, or collection for NFT gates
+    pub gate_min_balance: u64,
+    pub reserved_by: [Pubkey; 9],
+    pub reserved_until: [i64; 9],
+    pub time_bank_used: [u32; 9], // Seconds of `TIME_BANK` each seat has spent
+    pub waitlist_len: u8, // Mirrors the waitlist so `join_table` can tell without loading it
+    pub leaving: [u8; 9], // Seats to remove once the hand is settled
+    pub run_twice: [u8; 9], // Run-it-twice votes for the current hand
+    pub second_board: [u8; 5],
+    pub ran_twice: u8, // Whether `second_board` holds a runout
+    pub muck: [u8; 9], // Seats that muck losing hands at showdown
+    pub show_decision: [u8; 9], // This hand's show-or-muck decisions: 0 undecided, 1 show, 2 muck
+    pub last_aggressor: u8,
+    pub has_aggressor: u8, // Whether `last_aggressor` is set
+    pub jackpot: u8, // Whether pots pay into and bad beats draw from the `Jackpot`
+    pub preflop_stats: [u8; 9], // This hand's `PREFLOP_VPIP`/`PREFLOP_PFR` flags per seat
+    pub _padding: [u8; 5],
+}
+
+impl Default for Table {
+    /// An empty table with no cards, players or current player
+    fn default() -> Self {
+        let mut table = Self::zeroed();
+        table.version = Table::VERSION;
+        table.current_player = NONE;
+        table.community_cards = [NONE; 5];
+        table
+    }
 }
 
 impl Table {
-    pub const MAX_SIZE: usize = 8 + // discriminator
-        32 + // creator
-        8 + // table_id
-        8 + // small_blind
-        8 + // big_blind
-        1 + // max_players
-        8 + // buy_in
-        1 + // game_state
-        1 + // dealer_position
-        1 + // small_blind_position
-        1 + // big_blind_position
-        1 + 32 + // current_player (Option<u8>)
-        8 + // pot
-        (1 + 2) * 5 + // community_cards [Option<Card>; 5]
-        1 + // round
-        8 + // last_raise_amount
-        8 + // min_raise
-        (1 + Player::MAX_SIZE) * 9 + // players [Option<Player>; 9]
-        1 + // player_count
-        8 + // created_at
-        8 + // last_action_at
-        8; // action_timeout
-
-    pub fn get_player(&self, seat: u8) -> Option<&Player> {
-        if seat as usize >= self.players.len() {
+    pub const SEED: &'static [u8] = b"table";
+
+    /// Layout version written by this program; older tables must go through `migrate_table`
+    pub const VERSION: u8 = 1;
+
+    /// `preflop_stats` flag for a seat that put chips in voluntarily pre-flop
+    pub const PREFLOP_VPIP: u8 = 1;
+
+    /// `preflop_stats` flag for a seat that raised pre-flop
+    pub const PREFLOP_PFR: u8 = 2;
+
+    pub const MAX_SIZE: usize = 8 + std::mem::size_of::<Table>(); // discriminator + fields
+
+    /// Seconds a seat reservation lasts, from `reserve_seat` or the waitlist
+    pub const RESERVATION_TIMEOUT: i64 = 120;
+
+    /// Seconds without a game action after which the admin may force-close the table
+    pub const ABANDON_TIMEOUT: i64 = 7 * 24 * 60 * 60;
+
+    /// Seconds each player may spend past `action_timeout` before being timed out
+    pub const TIME_BANK: u32 = 60;
+
+    /// Time banks are topped up at the start of every this many hands
+    pub const TIME_BANK_HANDS: u64 = 10;
+
+    /// Layout version of raw table account data, if it is one this program can read
+    pub fn layout_version(data: &[u8]) -> Option<u8> {
+        if data.len() < 8 || data[..8] != Table::DISCRIMINATOR {
             return None;
         }
-        self.players[seat as usize].as_ref()
+        match data.len() {
+            Table::MAX_SIZE if data[8] == Table::VERSION => Some(Table::VERSION),
+            crate::migration::LEGACY_TABLE_SIZE => Some(crate::migration::LEGACY_TABLE_VERSION),
+            _ => None,
+        }
     }
 
-    pub fn get_player_mut(&mut self, seat: u8) -> Option<&mut Player> {
-        if seat as usize >= self.players.len() {
-            return None;
+    /// Whether the account holds the current table layout
+    pub fn is_current(info: &AccountInfo) -> bool {
+        info.try_borrow_data()
+            .is_ok_and(|data| Table::layout_version(&data) == Some(Table::VERSION))
+    }
+
+    pub fn game_state(&self) -> GameState {
+        GameState::from_u8(self.game_state)
+    }
+
+    pub fn set_game_state(&mut self, state: GameState) {
+        self.game_state = state as u8;
+    }
+
+    pub fn round(&self) -> Round {
+        Round::from_u8(self.round)
+    }
+
+    pub fn set_round(&mut self, round: Round) {
+        self.round = round as u8;
+    }
+
+    pub fn current_player(&self) -> Option<u8> {
+        (self.current_player != NONE).then_some(self.current_player)
+    }
+
+    pub fn set_current_player(&mut self, seat: Option<u8>) {
+        self.current_player = seat.unwrap_or(NONE);
+    }
+
+    pub fn access(&self) -> TableAccess {
+        match self.access {
+            1 => TableAccess::Allowlist,
+            2 => TableAccess::Password { hash: self.password_hash },
+            _ => TableAccess::Public,
+        }
+    }
+
+    pub fn set_access(&mut self, access: TableAccess) {
+        self.access = access.to_u8();
+        self.password_hash = match access {
+            TableAccess::Password { hash } => hash,
+            _ => [0; 32],
+        };
+    }
+
+    pub fn gate(&self) -> Option<TokenGate> {
+        match self.gate {
+            1 => Some(TokenGate::Token { mint: self.gate_mint, min_balance: self.gate_min_balance }),
+            2 => Some(TokenGate::Collection { collection: self.gate_mint }),
+            _ => None,
         }
-        self.players[seat as usize].as_mut()
     }
 
-    pub fn get_active_players(&self) -> Vec<(u8, &Player)> {
+    pub fn set_gate(&mut self, gate: Option<TokenGate>) {
+        (self.gate, self.gate_mint, self.gate_min_balance) = match gate {
+            None => (0, Pubkey::default(), 0),
+            Some(TokenGate::Token { mint, min_balance }) => (1, mint, min_balance),
+            Some(TokenGate::Collection { collection }) => (2, collection, 1),
+        };
+    }
+
+    /// The hash a password table stores: the password salted with the table address
+    pub fn password_commitment(table: &Pubkey, password: &[u8]) -> [u8; 32] {
+        hashv(&[table.as_ref(), password]).to_bytes()
+    }
+
+    pub fn community_cards(&self) -> [Option<Card>; 5] {
+        self.community_cards.map(Card::from_slot)
+    }
+
+    pub fn set_community_cards(&mut self, cards: [Option<Card>; 5]) {
+        self.community_cards = cards.map(Card::to_slot);
+    }
+
+    /// The second runout when the hand is being run twice
+    pub fn second_board(&self) -> Option<[Option<Card>; 5]> {
+        (self.ran_twice != 0).then(|| self.second_board.map(Card::from_slot))
+    }
+
+    pub fn set_second_board(&mut self, board: Option<[Option<Card>; 5]>) {
+        self.ran_twice = board.is_some() as u8;
+        self.second_board = board.unwrap_or([None; 5]).map(Card::to_slot);
+    }
+
+    /// Decoded copy of every seat
+    pub fn players(&self) -> [Option<Player>; 9] {
+        core::array::from_fn(|seat| self.get_player(seat as u8))
+    }
+
+    pub fn get_player(&self, seat: u8) -> Option<Player> {
+        self.players.get(seat as usize).and_then(|s| s.player(seat))
+    }
+
+    pub fn set_player(&mut self, seat: u8, player: Option<&Player>) {
+        if let Some(slot) = self.players.get_mut(seat as usize) {
+            *slot = player.map_or_else(Seat::default, Seat::from_player);
+        }
+    }
+
+    pub fn find_seat(&self, pubkey: &Pubkey) -> Option<u8> {
         self.players
             .iter()
-            .enumerate()
-            .filter_map(|(i, p)| p.as_ref().map(|p| (i as u8, p)))
-            .filter(|(_, p)| p.is_active())
-            .collect()
+            .position(|s| s.is_occupied() && &s.pubkey == pubkey)
+            .map(|seat| seat as u8)
     }
 
-    pub fn get_next_active_player(&self, from_seat: u8) -> Option<u8> {
-        let active_players: Vec<u8> = self.get_active_players().iter().map(|(s, _)| *s).collect();
-        if active_players.is_empty() {
-            return None;
+    /// Who holds `seat` at time `now`, if anyone
+    pub fn reservation(&self, seat: u8, now: i64) -> Option<Pubkey> {
+        let seat = seat as usize;
+        (seat < self.reserved_by.len() && self.reserved_until[seat] > now).then(|| self.reserved_by[seat])
+    }
+
+    pub fn reserve_seat(&mut self, seat: u8, player: Pubkey, until: i64) {
+        if let Some(slot) = self.reserved_by.get_mut(seat as usize) {
+            *slot = player;
+            self.reserved_until[seat as usize] = until;
         }
+    }
 
-        // Find next player after from_seat
-        for &seat in &active_players {
-            if seat > from_seat {
-                return Some(seat);
+    pub fn clear_reservation(&mut self, seat: u8) {
+        self.reserve_seat(seat, Pubkey::default(), 0);
+    }
+
+    /// Seconds of time bank `seat` has left
+    pub fn time_bank(&self, seat: u8) -> u32 {
+        self.time_bank_used
+            .get(seat as usize)
+            .map_or(0, |&used| Table::TIME_BANK.saturating_sub(used))
+    }
+
+    /// Charge any time `seat` took past `action_timeout` to their time bank,
+    /// failing once the bank can't cover it
+    pub fn charge_time_bank(&mut self, seat: u8, now: i64) -> Result<()> {
+        let overtime = now - self.last_action_at - self.action_timeout;
+        if overtime > 0 {
+            require!(overtime <= self.time_bank(seat) as i64, PokerError::ActionTimeout);
+            self.time_bank_used[seat as usize] += overtime as u32;
+        }
+        Ok(())
+    }
+
+    pub fn reset_time_bank(&mut self, seat: u8) {
+        if let Some(used) = self.time_bank_used.get_mut(seat as usize) {
+            *used = 0;
+        }
+    }
+
+    /// Latch `seat`'s VPIP and PFR for this hand from a pre-flop action
+    pub fn record_preflop(&mut self, seat: u8, action_type: ActionType, raised: bool) {
+        if let Some(stats) = self.preflop_stats.get_mut(seat as usize) {
+            if matches!(action_type, ActionType::Call | ActionType::Raise | ActionType::AllIn) {
+                *stats |= Table::PREFLOP_VPIP;
+            }
+            if raised {
+                *stats |= Table::PREFLOP_PFR;
             }
         }
-        // Wrap around to first player
-        active_players.first().copied()
     }
 
-    pub fn get_active_player_count(&self) -> u8 {
-        self.get_active_players().len() as u8
+    pub fn last_aggressor(&self) -> Option<u8> {
+        (self.has_aggressor != 0).then_some(self.last_aggressor)
+    }
+
+    pub fn set_last_aggressor(&mut self, seat: Option<u8>) {
+        self.has_aggressor = seat.is_some() as u8;
+        self.last_aggressor = seat.unwrap_or(0);
+    }
+
+    pub fn show_decision(&self, seat: u8) -> Option<bool> {
+        match self.show_decision.get(seat as usize) {
+            Some(1) => Some(true),
+            Some(2) => Some(false),
+            _ => None,
+        }
+    }
+
+    pub fn set_show_decision(&mut self, seat: u8, show: Option<bool>) {
+        if let Some(decision) = self.show_decision.get_mut(seat as usize) {
+            *decision = match show {
+                None => 0,
+                Some(true) => 1,
+                Some(false) => 2,
+            };
+        }
+    }
+
+    pub fn mucks(&self, seat: u8) -> bool {
+        self.muck.get(seat as usize).is_some_and(|&flag| flag != 0)
+    }
+
+    pub fn set_muck(&mut self, seat: u8, muck: bool) {
+        if let Some(flag) = self.muck.get_mut(seat as usize) {
+            *flag = muck as u8;
+        }
+    }
+
+    pub fn is_leaving(&self, seat: u8) -> bool {
+        self.leaving.get(seat as usize).is_some_and(|&flag| flag != 0)
+    }
+
+    pub fn set_leaving(&mut self, seat: u8, leaving: bool) {
+        if let Some(flag) = self.leaving.get_mut(seat as usize) {
+            *flag = leaving as u8;
+        }
+    }
+
+    /// The seat `player` gets when joining without picking one: the seat they
+    /// hold, else the lowest free seat nobody else holds
+    pub fn first_free_seat(&self, player: &Pubkey, now: i64) -> Option<u8> {
+        let seats = 0..self.max_players.min(9);
+        seats.clone()
+            .find(|&seat| self.reservation(seat, now) == Some(*player))
+            .or_else(|| {
+                seats.into_iter().find(|&seat| !self.players[seat as usize].is_occupied() && self.reservation(seat, now).is_none())
+            })
     }
 }
 
-/// Player state at a table
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
-pub struct Player {
+/// Table loading for account constraints, which run before any handler code
+pub trait LoadTable {
+    /// Like `load`, but fails with `UnsupportedAccountVersion` instead of
+    /// reading an older layout as the current one
+    fn load_current(&self) -> Result<std::cell::Ref<'_, Table>>;
+}
+
+impl<'info> LoadTable for AccountLoader<'info, Table> {
+    fn load_current(&self) -> Result<std::cell::Ref<'_, Table>> {
+        require!(Table::is_current(self.as_ref()), PokerError::UnsupportedAccountVersion);
+        self.load()
+    }
+}
+
+// Accounts created with `init` go through a system program CPI, which caps their size
+const _: () = assert!(Table::MAX_SIZE <= MAX_PERMITTED_DATA_INCREASE);
+const _: () = assert!(PlayerProfile::MAX_SIZE <= MAX_PERMITTED_DATA_INCREASE);
+const _: () = assert!(TableAllowlist::MAX_SIZE <= MAX_PERMITTED_DATA_INCREASE);
+const _: () = assert!(TableWaitlist::MAX_SIZE <= MAX_PERMITTED_DATA_INCREASE);
+const _: () = assert!(SessionToken::MAX_SIZE <= MAX_PERMITTED_DATA_INCREASE);
+const _: () = assert!(GasSponsor::MAX_SIZE <= MAX_PERMITTED_DATA_INCREASE);
+const _: () = assert!(Jackpot::MAX_SIZE <= MAX_PERMITTED_DATA_INCREASE);
+// Changing the seat layout changes every table's layout
+const _: () = assert!(std::mem::size_of::<Seat>() == 88);
+
+/// Zero-copy storage for one seat of a `Table`
+#[zero_copy]
+#[derive(Default)]
+pub struct Seat {
     pub pubkey: Pubkey,
-    pub seat: u8,
     pub stack: u64,
     pub bet: u64,
-    pub hole_cards: [Option<Card>; 2],
     pub hole_cards_committed: [u8; 32], // Commitment hash for fairness
-    pub is_all_in: bool,
-    pub has_folded: bool,
-    pub has_acted: bool,
-    pub last_action: Option<ActionType>,
+    pub hole_cards: [u8; 2],
+    pub occupied: u8,
+    pub seat: u8,
+    pub is_all_in: u8,
+    pub has_folded: u8,
+    pub has_acted: u8,
+    pub last_action: u8,
+}
+
+impl Seat {
+    pub fn is_occupied(&self) -> bool {
+        self.occupied != 0
+    }
+
+    pub fn hole_cards(&self) -> [Option<Card>; 2] {
+        self.hole_cards.map(Card::from_slot)
+    }
+
+    pub fn player(&self, seat: u8) -> Option<Player> {
+        self.is_occupied().then(|| Player {
+            pubkey: self.pubkey,
+            seat,
+            stack: self.stack,
+            bet: self.bet,
+            hole_cards: self.hole_cards(),
+            hole_cards_committed: self.hole_cards_committed,
+            is_all_in: self.is_all_in != 0,
+            has_folded: self.has_folded != 0,
+            has_acted: self.has_acted != 0,
+            last_action: (self.last_action != NONE).then(|| ActionType::from_u8(self.last_action)),
+        })
+    }
+
+    pub fn from_player(player: &Player) -> Self {
+        Self {
+            pubkey: player.pubkey,
+            stack: player.stack,
+            bet: player.bet,
+            hole_cards_committed: player.hole_cards_committed,
+            hole_cards: player.hole_cards.map(Card::to_slot),
+            occupied: 1,
+            seat: player.seat,
+            is_all_in: player.is_all_in as u8,
+            has_folded: player.has_folded as u8,
+            has_acted: player.has_acted as u8,
+            last_action: player.last_action.map_or(NONE, |a| a as u8),
+        }
+    }
+}
+
+/// Lifetime statistics for a wallet, shared by every table it plays at
+#[account]
+#[derive(InitSpace)]
+pub struct PlayerProfile {
+    pub version: u8,
+    pub player: Pubkey,
+    pub hands_played: u64,
+    pub hands_won: u64,
+    pub vpip_hands: u64, // Hands where chips were put in voluntarily pre-flop
+    pub pfr_hands: u64, // Hands raised pre-flop
+    pub total_wagered: u64,
+    pub net_winnings: i64,
+    pub showdowns: u64,
+    pub bump: u8,
 }
 
-impl Player {
-    pub const MAX_SIZE: usize = 32 + // pubkey
-        1 + // seat
-        8 + // stack
-        8 + // bet
-        (1 + 2) * 2 + // hole_cards [Option<Card>; 2]
-        32 + // hole_cards_committed
-        1 + // is_all_in
-        1 + // has_folded
-        1 + // has_acted
-        1 + 1; // last_action (Option<ActionType>)
+impl PlayerProfile {
+    pub const SEED: &'static [u8] = b"profile";
+
+    pub const VERSION: u8 = 1;
 
-    pub fn is_active(&self) -> bool {
-        !self.has_folded && self.stack > 0
+    pub const MAX_SIZE: usize = 8 + PlayerProfile::INIT_SPACE; // discriminator + fields
+
+    /// Fill in a profile created by `init_if_needed`, and check an existing one's version
+    pub fn init_if_new(&mut self, player: Pubkey, bump: u8) -> Result<()> {
+        if self.player == Pubkey::default() {
+            self.version = PlayerProfile::VERSION;
+            self.player = player;
+            self.bump = bump;
+        }
+        require!(self.version == PlayerProfile::VERSION, PokerError::UnsupportedAccountVersion);
+        Ok(())
     }
 
-    pub fn can_act(&self, table: &Table) -> bool {
-        if !self.is_active() {
-            return false;
+    /// Fold a settled hand into the totals, with the seat's `Table::preflop_stats`
+    pub fn record_hand(&mut self, contributed: u64, payout: u64, showdown: bool, preflop: u8) -> Result<()> {
+        self.hands_played += 1;
+        if preflop & Table::PREFLOP_VPIP != 0 {
+            self.vpip_hands += 1;
         }
-        if self.is_all_in {
-            return false;
+        if preflop & Table::PREFLOP_PFR != 0 {
+            self.pfr_hands += 1;
         }
-        // Check if it's this player's turn
-        if let Some(current) = table.current_player {
-            if current == self.seat {
-                return true;
-            }
+        if payout > 0 {
+            self.hands_won += 1;
+        }
+        if showdown {
+            self.showdowns += 1;
         }
-        false
+        self.total_wagered = self
+            .total_wagered
+            .checked_add(contributed)
+            .ok_or(PokerError::MathOverflow)?;
+        let net = i64::try_from(payout as i128 - contributed as i128)
+            .map_err(|_| PokerError::MathOverflow)?;
+        self.net_winnings = self
+            .net_winnings
+            .checked_add(net)
+            .ok_or(PokerError::MathOverflow)?;
+        Ok(())
     }
 }
 
+/// Player state at a table, decoded from a `Seat`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub struct Player {
+    pub pubkey: Pubkey,
+    pub seat: u8,
+    pub stack: u64,
+    pub bet: u64,
+    pub hole_cards: [Option<Card>; 2],
+    pub hole_cards_committed: [u8; 32], // Commitment hash for fairness
+    pub is_all_in: bool,
+    pub has_folded: bool,
+    pub has_acted: bool,
+    pub last_action: Option<ActionType>,
+}
+
 /// Playing card
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
 pub struct Card {
@@ -164,6 +515,15 @@ impl Card {
             rank: Rank::from_u8(value % 13),
         }
     }
+
+    /// Zero-copy encoding: the card index, or `NONE`
+    pub fn to_slot(card: Option<Card>) -> u8 {
+        card.map_or(NONE, |c| c.to_u8())
+    }
+
+    pub fn from_slot(value: u8) -> Option<Card> {
+        (value != NONE).then(|| Card::from_u8(value))
+    }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
@@ -224,6 +584,222 @@ impl Rank {
     }
 }
 
+/// Wallets invited to an allowlist table, kept apart from the table so public
+/// tables don't pay for the space
+#[account]
+#[derive(InitSpace)]
+pub struct TableAllowlist {
+    pub version: u8,
+    pub table: Pubkey,
+    #[max_len(64)]
+    pub invitees: Vec<Pubkey>,
+    pub bump: u8,
+}
+
+impl TableAllowlist {
+    pub const SEED: &'static [u8] = b"allowlist";
+
+    pub const VERSION: u8 = 1;
+
+    pub const MAX_INVITEES: usize = 64; // Keep in step with `max_len` above
+
+    pub const MAX_SIZE: usize = 8 + TableAllowlist::INIT_SPACE; // discriminator + fields
+}
+
+/// Wallets queued for a seat at a full table, first in line first
+#[account]
+#[derive(InitSpace)]
+pub struct TableWaitlist {
+    pub version: u8,
+    pub table: Pubkey,
+    #[max_len(16)]
+    pub entries: Vec<WaitlistEntry>,
+    pub bump: u8,
+}
+
+impl TableWaitlist {
+    pub const SEED: &'static [u8] = b"waitlist";
+
+    pub const VERSION: u8 = 1;
+
+    pub const MAX_ENTRIES: usize = 16; // Keep in step with `max_len` above
+
+    pub const MAX_SIZE: usize = 8 + TableWaitlist::INIT_SPACE; // discriminator + fields
+
+    pub fn position(&self, player: &Pubkey) -> Option<usize> {
+        self.entries.iter().position(|e| &e.player == player)
+    }
+
+    /// Remove the entry at `index` and pay its deposit to `to`
+    pub fn remove_entry<'info>(
+        waitlist: &mut Account<'info, TableWaitlist>,
+        index: usize,
+        to: &AccountInfo<'info>,
+    ) -> Result<WaitlistEntry> {
+        let entry = waitlist.entries.remove(index);
+        if entry.deposit > 0 {
+            let from = waitlist.to_account_info();
+            **from.try_borrow_mut_lamports()? -= entry.deposit;
+            **to.try_borrow_mut_lamports()? += entry.deposit;
+        }
+        Ok(entry)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, InitSpace)]
+pub struct WaitlistEntry {
+    pub player: Pubkey,
+    /// Lamports held by the waitlist account. Depositors are seated as soon as
+    /// a seat frees up, and get the deposit back when seated or on leaving.
+    pub deposit: u64,
+}
+
+/// An ephemeral key a player has authorized to act for them at one table.
+/// Session keys can take game actions but never move funds.
+#[account]
+#[derive(InitSpace)]
+pub struct SessionToken {
+    pub version: u8,
+    pub table: Pubkey,
+    pub player: Pubkey,
+    pub session_key: Pubkey,
+    pub expires_at: i64,
+    pub bump: u8,
+}
+
+impl SessionToken {
+    pub const SEED: &'static [u8] = b"session";
+
+    pub const VERSION: u8 = 1;
+
+    /// Longest a session may last, in seconds
+    pub const MAX_DURATION: i64 = 24 * 60 * 60;
+
+    pub const MAX_SIZE: usize = 8 + SessionToken::INIT_SPACE; // discriminator + fields
+
+    /// Check that `signer` may act for `player`: either it is the wallet itself,
+    /// or `session` authorizes it and hasn't expired
+    pub fn authorize(session: Option<&SessionToken>, player: &Pubkey, signer: &Pubkey, now: i64) -> Result<()> {
+        if signer == player {
+            return Ok(());
+        }
+        let session = session.ok_or(PokerError::InvalidSessionKey)?;
+        require!(session.version == SessionToken::VERSION, PokerError::UnsupportedAccountVersion);
+        require_keys_eq!(session.session_key, *signer, PokerError::InvalidSessionKey);
+        require!(now < session.expires_at, PokerError::SessionExpired);
+        Ok(())
+    }
+}
+
+/// Lamports a table's creator sets aside to pay back a relayer for the fees
+/// of the gameplay transactions it submits. The program takes no rake, so
+/// the creator funds it directly rather than out of pots.
+#[account]
+#[derive(InitSpace)]
+pub struct GasSponsor {
+    pub version: u8,
+    pub table: Pubkey,
+    pub relayer: Pubkey,
+    pub reimbursement: u64, // Lamports per transaction
+    pub bump: u8,
+}
+
+impl GasSponsor {
+    pub const SEED: &'static [u8] = b"sponsor";
+
+    pub const VERSION: u8 = 1;
+
+    pub const MAX_SIZE: usize = 8 + GasSponsor::INIT_SPACE; // discriminator + fields
+
+    /// Pay `relayer` one reimbursement if the sponsor can cover it above its
+    /// rent reserve; returns what was paid. Running dry never blocks play.
+    pub fn reimburse<'info>(sponsor: &Account<'info, GasSponsor>, relayer: &AccountInfo<'info>) -> Result<u64> {
+        require!(sponsor.version == GasSponsor::VERSION, PokerError::UnsupportedAccountVersion);
+        require_keys_eq!(sponsor.relayer, relayer.key(), PokerError::InvalidRelayer);
+
+        let from = sponsor.to_account_info();
+        let reserve = Rent::get()?.minimum_balance(from.data_len());
+        if from.lamports().saturating_sub(reserve) < sponsor.reimbursement {
+            return Ok(0);
+        }
+        **from.try_borrow_mut_lamports()? -= sponsor.reimbursement;
+        **relayer.try_borrow_mut_lamports()? += sponsor.reimbursement;
+        Ok(sponsor.reimbursement)
+    }
+}
+
+/// Program-wide bad-beat jackpot. Tables that opt in pay a drop from every
+/// pot that sees a flop, and a qualifying bad beat pays out the whole balance.
+#[account]
+#[derive(InitSpace)]
+pub struct Jackpot {
+    pub version: u8,
+    pub balance: u64, // Chips, like table stacks
+    pub drop_bps: u16, // Share of each pot paid in
+    pub loser_bps: u16,
+    pub winner_bps: u16,
+    pub table_bps: u16, // Split evenly between everyone else dealt in
+    pub bump: u8,
+}
+
+impl Jackpot {
+    pub const SEED: &'static [u8] = b"jackpot";
+
+    pub const VERSION: u8 = 1;
+
+    pub const MAX_SIZE: usize = 8 + Jackpot::INIT_SPACE; // discriminator + fields
+
+    pub const MAX_DROP_BPS: u16 = 500;
+
+    /// The drop is capped and the payout shares must cover the whole balance
+    pub fn validate(drop_bps: u16, loser_bps: u16, winner_bps: u16, table_bps: u16) -> Result<()> {
+        require!(drop_bps <= Self::MAX_DROP_BPS, PokerError::InvalidJackpotConfig);
+        let shares = u32::from(loser_bps) + u32::from(winner_bps) + u32::from(table_bps);
+        require!(shares == 10_000, PokerError::InvalidJackpotConfig);
+        Ok(())
+    }
+
+    /// Payouts of the current balance to the loser, the winner and each of
+    /// `others` players. The table share stays in the jackpot if nobody
+    /// else was dealt in, as do rounding leftovers.
+    pub fn shares(&self, others: u64) -> (u64, u64, u64) {
+        let share = |bps: u16| (u128::from(self.balance) * u128::from(bps) / 10_000) as u64;
+        let each = share(self.table_bps).checked_div(others).unwrap_or(0);
+        (share(self.loser_bps), share(self.winner_bps), each)
+    }
+}
+
+/// Who may join a table; the creator may always sit at their own table
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum TableAccess {
+    Public,
+    /// Wallets in the table's `TableAllowlist`
+    Allowlist,
+    /// Anyone presenting the password behind `Table::password_commitment`.
+    /// The password is visible on-chain once someone joins with it.
+    Password { hash: [u8; 32] },
+}
+
+impl TableAccess {
+    pub fn to_u8(&self) -> u8 {
+        match self {
+            TableAccess::Public => 0,
+            TableAccess::Allowlist => 1,
+            TableAccess::Password { .. } => 2,
+        }
+    }
+}
+
+/// Holdings a wallet needs to join a table, checked against the token
+/// account passed to `join_table`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum TokenGate {
+    /// At least `min_balance` base units of `mint`
+    Token { mint: Pubkey, min_balance: u64 },
+    /// An NFT from a verified Metaplex collection
+    Collection { collection: Pubkey },
+}
+
 /// Game state
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
 pub enum GameState {
@@ -237,6 +813,21 @@ pub enum GameState {
     Finished = 7,
 }
 
+impl GameState {
+    pub fn from_u8(value: u8) -> Self {
+        match value {
+            1 => GameState::Starting,
+            2 => GameState::PreFlop,
+            3 => GameState::Flop,
+            4 => GameState::Turn,
+            5 => GameState::River,
+            6 => GameState::Showdown,
+            7 => GameState::Finished,
+            _ => GameState::Waiting,
+        }
+    }
+}
+
 /// Betting round
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
 pub enum Round {
@@ -246,6 +837,17 @@ pub enum Round {
     River = 3,
 }
 
+impl Round {
+    pub fn from_u8(value: u8) -> Self {
+        match value {
+            1 => Round::Flop,
+            2 => Round::Turn,
+            3 => Round::River,
+            _ => Round::PreFlop,
+        }
+    }
+}
+
 /// Player action type
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
 pub enum ActionType {
@@ -256,6 +858,18 @@ pub enum ActionType {
     AllIn = 4,
 }
 
+impl ActionType {
+    pub fn from_u8(value: u8) -> Self {
+        match value {
+            1 => ActionType::Check,
+            2 => ActionType::Call,
+            3 => ActionType::Raise,
+            4 => ActionType::AllIn,
+            _ => ActionType::Fold,
+        }
+    }
+}
+
 /// Events emitted by the program
 #[event]
 pub struct PlayerJoinedEvent {
@@ -265,6 +879,45 @@ pub struct PlayerJoinedEvent {
     pub stack: u64,
 }
 
+#[event]
+pub struct PlayerLeftEvent {
+    pub table_id: u64,
+    pub player: Pubkey,
+    pub seat: u8,
+    pub stack: u64,
+}
+
+#[event]
+pub struct SessionStartedEvent {
+    pub table_id: u64,
+    pub player: Pubkey,
+    pub session_key: Pubkey,
+    pub expires_at: i64,
+}
+
+#[event]
+pub struct RelayerReimbursedEvent {
+    pub table_id: u64,
+    pub relayer: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct WaitlistJoinedEvent {
+    pub table_id: u64,
+    pub player: Pubkey,
+    pub position: u8,
+    pub deposit: u64,
+}
+
+#[event]
+pub struct SeatReservedEvent {
+    pub table_id: u64,
+    pub player: Pubkey,
+    pub seat: u8,
+    pub expires_at: i64,
+}
+
 #[event]
 pub struct GameStartedEvent {
     pub table_id: u64,
@@ -282,6 +935,7 @@ pub struct ActionTakenEvent {
     pub amount: u64,
     pub new_pot: u64,
     pub round: Round,
+    pub time_bank: u32, // Seconds the player has left
 }
 
 #[event]
@@ -298,6 +952,80 @@ pub struct PotDistributedEvent {
     pub amounts: Vec<u64>,
 }
 
+#[event]
+pub struct TableClosedEvent {
+    pub table_id: u64,
+    pub creator: Pubkey,
+    pub forced: bool,
+}
+
+#[event]
+pub struct TableMigratedEvent {
+    pub table_id: u64,
+    pub from_version: u8,
+    pub to_version: u8,
+}
+
+#[event]
+pub struct RunTwiceVoteEvent {
+    pub table_id: u64,
+    pub player: Pubkey,
+    pub seat: u8,
+    pub agree: bool,
+}
+
+#[event]
+pub struct BoardRunTwiceEvent {
+    pub table_id: u64,
+    pub first_board: [Option<Card>; 5],
+    pub second_board: [Option<Card>; 5],
+}
+
+#[event]
+pub struct JackpotPaidEvent {
+    pub table_id: u64,
+    pub loser: Pubkey,
+    pub winner: Pubkey,
+    pub loser_amount: u64,
+    pub winner_amount: u64,
+    /// Paid to each other player dealt into the hand
+    pub table_amount: u64,
+}
+
+#[event]
+pub struct UncalledBetReturnedEvent {
+    pub table_id: u64,
+    pub player: Pubkey,
+    pub seat: u8,
+    /// Chips moved from the pot back to the player's stack
+    pub amount: u64,
+}
+
+#[event]
+pub struct RabbitHuntEvent {
+    pub table_id: u64,
+    /// The community cards, followed by the streets the hand never reached
+    pub board: [Option<Card>; 5],
+    pub dealt: u8,
+}
+
+/// How one player's hand went at showdown
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub struct ShowdownDecision {
+    pub seat: u8,
+    pub player: Pubkey,
+    pub shown: bool,
+}
+
+/// Summary of a hand that reached showdown, with players in showdown order
+#[event]
+pub struct HandHistoryEvent {
+    pub table_id: u64,
+    pub hand_number: u64,
+    pub community_cards: [Option<Card>; 5],
+    pub showdown: Vec<ShowdownDecision>,
+}
+
 #[event]
 pub struct CardRevealedEvent {
     pub table_id: u64,
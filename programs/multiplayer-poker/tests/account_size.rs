@@ -0,0 +1,156 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::{AccountDeserialize, AccountSerialize, Discriminator};
+use multiplayer_poker::state::*;
+
+fn max_player(seat: u8) -> Player {
+    Player {
+        pubkey: Pubkey::new_from_array([0xFF; 32]),
+        seat,
+        stack: u64::MAX,
+        bet: u64::MAX,
+        hole_cards: [Some(Card::from_u8(50)), Some(Card::from_u8(51))],
+        hole_cards_committed: [0xFF; 32],
+        is_all_in: true,
+        has_folded: true,
+        has_acted: true,
+        last_action: Some(ActionType::AllIn),
+    }
+}
+
+/// Every field at its widest value and every seat occupied
+fn max_table() -> Table {
+    let mut table = Table {
+        creator: Pubkey::new_from_array([0xFF; 32]),
+        deck_seed: [0xFF; 32],
+        table_id: u64::MAX,
+        small_blind: u64::MAX,
+        big_blind: u64::MAX,
+        buy_in: u64::MAX,
+        pot: u64::MAX,
+        last_raise_amount: u64::MAX,
+        min_raise: u64::MAX,
+        created_at: i64::MIN,
+        last_action_at: i64::MIN,
+        action_timeout: i64::MIN,
+        hand_number: u64::MAX,
+        max_players: 9,
+        dealer_position: 8,
+        small_blind_position: 8,
+        big_blind_position: 8,
+        player_count: 9,
+        deck_position: 51,
+        bump: u8::MAX,
+        ..Table::default()
+    };
+    table.set_game_state(GameState::Finished);
+    table.set_round(Round::River);
+    table.set_current_player(Some(8));
+    table.set_community_cards([Some(Card::from_u8(51)); 5]);
+    table.set_access(TableAccess::Password { hash: [0xFF; 32] });
+    table.set_gate(Some(TokenGate::Token { mint: Pubkey::new_from_array([0xFF; 32]), min_balance: u64::MAX }));
+    for seat in 0..9 {
+        table.set_player(seat, Some(&max_player(seat)));
+        table.reserve_seat(seat, Pubkey::new_from_array([0xFF; 32]), i64::MAX);
+        table.set_leaving(seat, true);
+        table.set_muck(seat, true);
+        table.set_show_decision(seat, Some(false));
+    }
+    table.set_last_aggressor(Some(8));
+    table.jackpot = 1;
+    table.time_bank_used = [u32::MAX; 9];
+    table.run_twice = [1; 9];
+    table.set_second_board(Some([Some(Card::from_u8(51)); 5]));
+    table.waitlist_len = u8::MAX;
+    table
+}
+
+#[test]
+fn max_table_fits_allocated_space() {
+    let table = max_table();
+    let mut data = Table::DISCRIMINATOR.to_vec();
+    data.extend_from_slice(bytemuck::bytes_of(&table));
+    assert_eq!(data.len(), Table::MAX_SIZE);
+
+    let loaded = Table::try_deserialize(&mut data.as_slice()).unwrap();
+    assert_eq!(bytemuck::bytes_of(&loaded), bytemuck::bytes_of(&table));
+    assert_eq!(loaded.game_state(), GameState::Finished);
+    assert_eq!(loaded.round(), Round::River);
+    assert_eq!(loaded.current_player(), Some(8));
+    assert_eq!(loaded.community_cards(), [Some(Card::from_u8(51)); 5]);
+    assert_eq!(loaded.second_board(), Some([Some(Card::from_u8(51)); 5]));
+    assert_eq!(loaded.last_aggressor(), Some(8));
+    assert_eq!(loaded.jackpot, 1);
+    assert_eq!(loaded.access(), TableAccess::Password { hash: [0xFF; 32] });
+    assert_eq!(loaded.gate(), Some(TokenGate::Token { mint: Pubkey::new_from_array([0xFF; 32]), min_balance: u64::MAX }));
+    for seat in 0..9 {
+        assert_eq!(loaded.get_player(seat), Some(max_player(seat)));
+        assert!(loaded.is_leaving(seat));
+    }
+}
+
+#[test]
+fn empty_seats_and_options_decode_as_none() {
+    let mut table = max_table();
+    table.set_current_player(None);
+    table.set_community_cards([None; 5]);
+    table.set_second_board(None);
+    table.set_player(3, None);
+    let mut player = max_player(4);
+    player.hole_cards = [None; 2];
+    player.last_action = None;
+    table.set_player(4, Some(&player));
+
+    assert_eq!(table.current_player(), None);
+    assert_eq!(table.community_cards(), [None; 5]);
+    assert_eq!(table.second_board(), None);
+    assert_eq!(table.get_player(3), None);
+    assert_eq!(table.get_player(4), Some(player));
+    assert_eq!(table.get_player(9), None);
+    assert_eq!(table.find_seat(&player.pubkey), Some(0));
+}
+
+#[test]
+fn max_profile_fits_allocated_space() {
+    let profile = PlayerProfile {
+        version: u8::MAX,
+        player: Pubkey::new_from_array([0xFF; 32]),
+        hands_played: u64::MAX,
+        hands_won: u64::MAX,
+        vpip_hands: u64::MAX,
+        pfr_hands: u64::MAX,
+        total_wagered: u64::MAX,
+        net_winnings: i64::MIN,
+        showdowns: u64::MAX,
+        bump: u8::MAX,
+    };
+    let mut data = Vec::new();
+    profile.try_serialize(&mut data).unwrap();
+    assert_eq!(data.len(), PlayerProfile::MAX_SIZE);
+}
+
+#[test]
+fn full_allowlist_fits_allocated_space() {
+    let allowlist = TableAllowlist {
+        version: u8::MAX,
+        table: Pubkey::new_from_array([0xFF; 32]),
+        invitees: vec![Pubkey::new_from_array([0xFF; 32]); TableAllowlist::MAX_INVITEES],
+        bump: u8::MAX,
+    };
+    let mut data = Vec::new();
+    allowlist.try_serialize(&mut data).unwrap();
+    assert_eq!(data.len(), TableAllowlist::MAX_SIZE);
+}
+
+#[test]
+fn full_waitlist_fits_allocated_space() {
+    let entry = WaitlistEntry { player: Pubkey::new_from_array([0xFF; 32]), deposit: u64::MAX };
+    let waitlist = TableWaitlist {
+        version: u8::MAX,
+        table: Pubkey::new_from_array([0xFF; 32]),
+        entries: vec![entry; TableWaitlist::MAX_ENTRIES],
+        bump: u8::MAX,
+    };
+    let mut data = Vec::new();
+    waitlist.try_serialize(&mut data).unwrap();
+    assert_eq!(data.len(), TableWaitlist::MAX_SIZE);
+}
@@ -0,0 +1,646 @@
+//! In-process SVM harness shared by the integration tests.
+//!
+//! The tests load the compiled `multiplayer_poker.so`, so the compute budget,
+//! the SBF stack frame and heap limits and the loader's account serialization
+//! apply as they do on chain. Build it with `anchor build` first; the harness
+//! looks in `SBF_OUT_DIR` (or `BPF_OUT_DIR`) and falls back to
+//! `target/deploy`.
+#![allow(dead_code)]
+
+use anchor_lang::prelude::{Clock, Pubkey};
+use anchor_lang::{AccountDeserialize, InstructionData, ToAccountMetas};
+use multiplayer_poker::error::PokerError;
+use multiplayer_poker::state::{ActionType, GasSponsor, Jackpot, PlayerProfile, SessionToken, Table, TableAccess, TableAllowlist, TableWaitlist, TokenGate};
+use solana_program_test::{BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::account::Account;
+use solana_sdk::bpf_loader_upgradeable::{self, UpgradeableLoaderState};
+use solana_sdk::instruction::{AccountMeta, Instruction, InstructionError};
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::system_instruction;
+use solana_sdk::transaction::{Transaction, TransactionError};
+
+pub const SMALL_BLIND: u64 = 5;
+pub const BIG_BLIND: u64 = 10;
+pub const BUY_IN: u64 = 1_000;
+pub const WALLET_LAMPORTS: u64 = 10_000_000_000;
+pub const INSTRUCTIONS_SYSVAR: Pubkey = anchor_lang::solana_program::sysvar::instructions::ID;
+
+/// Point solana-program-test at the built program, failing early if it is missing
+fn locate_program() {
+    let out_dir = std::env::var_os("BPF_OUT_DIR")
+        .or_else(|| std::env::var_os("SBF_OUT_DIR"))
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(|| std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../../target/deploy"));
+    let program = out_dir.join("multiplayer_poker.so");
+    assert!(
+        program.is_file(),
+        "{} not found: run `anchor build` or set SBF_OUT_DIR to the directory holding it",
+        program.display()
+    );
+    std::env::set_var("SBF_OUT_DIR", out_dir);
+}
+
+pub struct Harness {
+    pub ctx: ProgramTestContext,
+    /// Upgrade authority recorded in the program's ProgramData
+    pub admin: Keypair,
+}
+
+impl Harness {
+    pub async fn start() -> Self {
+        locate_program();
+        let mut program_test = ProgramTest::new("multiplayer_poker", multiplayer_poker::ID, None);
+
+        // The program is loaded without ProgramData, so stand one in for the admin checks
+        let admin = Keypair::new();
+        let program_data = UpgradeableLoaderState::ProgramData {
+            slot: 0,
+            upgrade_authority_address: Some(admin.pubkey()),
+        };
+        program_test.add_account(
+            program_data_address(),
+            Account::new_data(1_000_000_000, &program_data, &bpf_loader_upgradeable::ID).unwrap(),
+        );
+
+        Self { ctx: program_test.start_with_context().await, admin }
+    }
+
+    pub fn payer(&self) -> Pubkey {
+        self.ctx.payer.pubkey()
+    }
+
+    /// A new keypair holding `WALLET_LAMPORTS`
+    pub async fn wallet(&mut self) -> Keypair {
+        let wallet = Keypair::new();
+        let transfer = system_instruction::transfer(&self.payer(), &wallet.pubkey(), WALLET_LAMPORTS);
+        self.send(&[transfer], &[]).await.unwrap();
+        wallet
+    }
+
+    /// Sign with the payer plus `signers` and process the transaction
+    pub async fn send(&mut self, instructions: &[Instruction], signers: &[&Keypair]) -> Result<(), BanksClientError> {
+        // A fresh blockhash keeps repeated identical instructions from being deduplicated
+        let blockhash = self.ctx.get_new_latest_blockhash().await.unwrap();
+        let mut all_signers = vec![&self.ctx.payer];
+        all_signers.extend_from_slice(signers);
+        let transaction =
+            Transaction::new_signed_with_payer(instructions, Some(&self.payer()), &all_signers, blockhash);
+        self.ctx.banks_client.process_transaction(transaction).await
+    }
+
+    pub async fn lamports(&mut self, address: &Pubkey) -> u64 {
+        self.ctx.banks_client.get_balance(*address).await.unwrap()
+    }
+
+    pub async fn table(&mut self, address: &Pubkey) -> Table {
+        self.account(address).await
+    }
+
+    pub async fn profile(&mut self, player: &Pubkey) -> PlayerProfile {
+        self.account(&profile_pda(player)).await
+    }
+
+    pub async fn account<T: AccountDeserialize>(&mut self, address: &Pubkey) -> T {
+        let account = self.ctx.banks_client.get_account(*address).await.unwrap().expect("account missing");
+        T::try_deserialize(&mut account.data.as_slice()).unwrap()
+    }
+
+    pub async fn clock(&mut self) -> Clock {
+        self.ctx.banks_client.get_sysvar::<Clock>().await.unwrap()
+    }
+
+    /// Move the cluster clock forward without advancing the slot
+    pub async fn warp_seconds(&mut self, seconds: i64) {
+        let mut clock = self.clock().await;
+        clock.unix_timestamp += seconds;
+        self.ctx.set_sysvar(&clock);
+    }
+
+    /// Create a table with the default blinds and buy-in and return its address
+    pub async fn create_table(&mut self, creator: &Keypair, table_id: u64) -> Pubkey {
+        let ix = create_table(&creator.pubkey(), table_id, 9, TableAccess::Public, None);
+        self.send(&[ix], &[creator]).await.unwrap();
+        table_pda(&creator.pubkey(), table_id)
+    }
+
+    /// Create a table and seat each of `players` in order from seat 0
+    pub async fn seated_table(&mut self, creator: &Keypair, players: &[&Keypair]) -> Pubkey {
+        let table = self.create_table(creator, 1).await;
+        for (seat, player) in players.iter().enumerate() {
+            self.send(&[join_table(&table, &player.pubkey(), seat as u8)], &[player]).await.unwrap();
+        }
+        table
+    }
+}
+
+pub fn program_data_address() -> Pubkey {
+    Pubkey::find_program_address(&[multiplayer_poker::ID.as_ref()], &bpf_loader_upgradeable::ID).0
+}
+
+pub fn table_pda(creator: &Pubkey, table_id: u64) -> Pubkey {
+    Pubkey::find_program_address(&[b"table", creator.as_ref(), &table_id.to_le_bytes()], &multiplayer_poker::ID).0
+}
+
+pub fn profile_pda(player: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[PlayerProfile::SEED, player.as_ref()], &multiplayer_poker::ID).0
+}
+
+fn instruction(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: multiplayer_poker::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+pub fn waitlist_pda(table: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[TableWaitlist::SEED, table.as_ref()], &multiplayer_poker::ID).0
+}
+
+pub fn session_pda(table: &Pubkey, player: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[SessionToken::SEED, table.as_ref(), player.as_ref()], &multiplayer_poker::ID).0
+}
+
+pub fn sponsor_pda(table: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[GasSponsor::SEED, table.as_ref()], &multiplayer_poker::ID).0
+}
+
+pub fn jackpot_pda() -> Pubkey {
+    Pubkey::find_program_address(&[Jackpot::SEED], &multiplayer_poker::ID).0
+}
+
+pub fn allowlist_pda(table: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[TableAllowlist::SEED, table.as_ref()], &multiplayer_poker::ID).0
+}
+
+/// A table with the default blinds and buy-in
+pub fn create_table(creator: &Pubkey, table_id: u64, max_players: u8, access: TableAccess, gate: Option<TokenGate>) -> Instruction {
+    instruction(
+        multiplayer_poker::accounts::CreateTable {
+            table: table_pda(creator, table_id),
+            creator: *creator,
+            system_program: anchor_lang::system_program::ID,
+        },
+        multiplayer_poker::instruction::CreateTable {
+            table_id,
+            small_blind: SMALL_BLIND,
+            big_blind: BIG_BLIND,
+            max_players,
+            buy_in: BUY_IN,
+            access,
+            gate,
+        },
+    )
+}
+
+pub fn join_table(table: &Pubkey, player: &Pubkey, seat: u8) -> Instruction {
+    join_private_table(table, player, seat, false, None)
+}
+
+/// Join at whichever seat the program picks
+pub fn join_any_seat(table: &Pubkey, player: &Pubkey) -> Instruction {
+    instruction(join_accounts(table, player), multiplayer_poker::instruction::JoinTable { seat: None, password: None })
+}
+
+pub fn reserve_seat(table: &Pubkey, player: &Pubkey, seat: Option<u8>) -> Instruction {
+    instruction(
+        multiplayer_poker::accounts::ReserveSeat {
+            table: *table,
+            player: *player,
+            allowlist: None,
+            gate_token_account: None,
+            gate_metadata: None,
+        },
+        multiplayer_poker::instruction::ReserveSeat { seat, password: None },
+    )
+}
+
+fn join_accounts(table: &Pubkey, player: &Pubkey) -> multiplayer_poker::accounts::JoinTable {
+    multiplayer_poker::accounts::JoinTable {
+        table: *table,
+        player: *player,
+        profile: profile_pda(player),
+        allowlist: None,
+        gate_token_account: None,
+        gate_metadata: None,
+        waitlist: None,
+        system_program: anchor_lang::system_program::ID,
+    }
+}
+
+/// Join passing the table's allowlist account and/or a password
+pub fn join_private_table(table: &Pubkey, player: &Pubkey, seat: u8, allowlist: bool, password: Option<&[u8]>) -> Instruction {
+    let accounts = multiplayer_poker::accounts::JoinTable {
+        allowlist: allowlist.then(|| allowlist_pda(table)),
+        ..join_accounts(table, player)
+    };
+    instruction(accounts, multiplayer_poker::instruction::JoinTable { seat: Some(seat), password: password.map(<[u8]>::to_vec) })
+}
+
+/// Join as the first waitlisted player, leaving the waitlist
+pub fn join_from_waitlist(table: &Pubkey, player: &Pubkey, seat: u8) -> Instruction {
+    let accounts = multiplayer_poker::accounts::JoinTable { waitlist: Some(waitlist_pda(table)), ..join_accounts(table, player) };
+    instruction(accounts, multiplayer_poker::instruction::JoinTable { seat: Some(seat), password: None })
+}
+
+/// Join presenting a token account, and NFT metadata for collection gates
+pub fn join_gated_table(table: &Pubkey, player: &Pubkey, seat: u8, token_account: &Pubkey, metadata: Option<Pubkey>) -> Instruction {
+    let accounts = multiplayer_poker::accounts::JoinTable {
+        gate_token_account: Some(*token_account),
+        gate_metadata: metadata,
+        ..join_accounts(table, player)
+    };
+    instruction(accounts, multiplayer_poker::instruction::JoinTable { seat: Some(seat), password: None })
+}
+
+pub fn leave_table(table: &Pubkey, player: &Pubkey) -> Instruction {
+    instruction(
+        multiplayer_poker::accounts::LeaveTable {
+            table: *table,
+            player: *player,
+            waitlist: None,
+            next_player: None,
+            system_program: anchor_lang::system_program::ID,
+        },
+        multiplayer_poker::instruction::LeaveTable {},
+    )
+}
+
+/// Leave a table with a waitlist, handing the seat to `next` (the first waitlisted wallet)
+pub fn leave_to_waitlist(table: &Pubkey, player: &Pubkey, next: &Pubkey) -> Instruction {
+    instruction(
+        multiplayer_poker::accounts::LeaveTable {
+            table: *table,
+            player: *player,
+            waitlist: Some(waitlist_pda(table)),
+            next_player: Some(*next),
+            system_program: anchor_lang::system_program::ID,
+        },
+        multiplayer_poker::instruction::LeaveTable {},
+    )
+}
+
+pub fn join_waitlist(table: &Pubkey, player: &Pubkey, deposit: bool) -> Instruction {
+    instruction(
+        multiplayer_poker::accounts::JoinWaitlist {
+            table: *table,
+            player: *player,
+            profile: profile_pda(player),
+            waitlist: waitlist_pda(table),
+            allowlist: None,
+            gate_token_account: None,
+            gate_metadata: None,
+            system_program: anchor_lang::system_program::ID,
+        },
+        multiplayer_poker::instruction::JoinWaitlist { deposit, password: None },
+    )
+}
+
+pub fn leave_waitlist(table: &Pubkey, player: &Pubkey) -> Instruction {
+    instruction(
+        multiplayer_poker::accounts::LeaveWaitlist { table: *table, player: *player, waitlist: waitlist_pda(table) },
+        multiplayer_poker::instruction::LeaveWaitlist {},
+    )
+}
+
+pub fn start_game(table: &Pubkey, authority: &Pubkey) -> Instruction {
+    start_game_with(table, authority, None)
+}
+
+/// `start_game` submitted by `relayer`, which the table's gas sponsor pays back
+pub fn sponsored_start_game(table: &Pubkey, authority: &Pubkey, relayer: &Pubkey) -> Instruction {
+    start_game_with(table, authority, Some(*relayer))
+}
+
+fn start_game_with(table: &Pubkey, authority: &Pubkey, relayer: Option<Pubkey>) -> Instruction {
+    instruction(
+        multiplayer_poker::accounts::StartGame {
+            table: *table,
+            authority: *authority,
+            relayer,
+            sponsor: relayer.map(|_| sponsor_pda(table)),
+            instructions: relayer.map(|_| INSTRUCTIONS_SYSVAR),
+        },
+        multiplayer_poker::instruction::StartGame {},
+    )
+}
+
+pub fn action(table: &Pubkey, player: &Pubkey, action_type: ActionType, amount: Option<u64>) -> Instruction {
+    action_accounts(table, player, player, None, None, action_type, amount)
+}
+
+/// `action` submitted by `relayer`, which the table's gas sponsor pays back
+pub fn sponsored_action(table: &Pubkey, player: &Pubkey, relayer: &Pubkey, action_type: ActionType) -> Instruction {
+    action_accounts(table, player, player, None, Some(*relayer), action_type, None)
+}
+
+/// `action` signed by `session_key` for `player`
+pub fn session_action(table: &Pubkey, player: &Pubkey, session_key: &Pubkey, action_type: ActionType) -> Instruction {
+    action_accounts(table, session_key, player, Some(session_pda(table, player)), None, action_type, None)
+}
+
+fn action_accounts(
+    table: &Pubkey,
+    signer: &Pubkey,
+    player: &Pubkey,
+    session: Option<Pubkey>,
+    relayer: Option<Pubkey>,
+    action_type: ActionType,
+    amount: Option<u64>,
+) -> Instruction {
+    instruction(
+        multiplayer_poker::accounts::Action {
+            table: *table,
+            signer: *signer,
+            player: *player,
+            session,
+            relayer,
+            sponsor: relayer.map(|_| sponsor_pda(table)),
+            instructions: relayer.map(|_| INSTRUCTIONS_SYSVAR),
+        },
+        multiplayer_poker::instruction::Action { action_type, amount },
+    )
+}
+
+pub fn create_session(table: &Pubkey, player: &Pubkey, session_key: &Pubkey, expires_at: i64) -> Instruction {
+    instruction(
+        multiplayer_poker::accounts::CreateSession {
+            table: *table,
+            player: *player,
+            session: session_pda(table, player),
+            system_program: anchor_lang::system_program::ID,
+        },
+        multiplayer_poker::instruction::CreateSession { session_key: *session_key, expires_at },
+    )
+}
+
+pub fn set_gas_sponsor(table: &Pubkey, creator: &Pubkey, relayer: &Pubkey, reimbursement: u64, deposit: u64) -> Instruction {
+    instruction(
+        multiplayer_poker::accounts::SetGasSponsor {
+            table: *table,
+            creator: *creator,
+            sponsor: sponsor_pda(table),
+            system_program: anchor_lang::system_program::ID,
+        },
+        multiplayer_poker::instruction::SetGasSponsor { relayer: *relayer, reimbursement, deposit },
+    )
+}
+
+pub fn close_gas_sponsor(table: &Pubkey, creator: &Pubkey) -> Instruction {
+    instruction(
+        multiplayer_poker::accounts::CloseGasSponsor { table: *table, creator: *creator, sponsor: sponsor_pda(table) },
+        multiplayer_poker::instruction::CloseGasSponsor {},
+    )
+}
+
+pub fn revoke_session(table: &Pubkey, player: &Pubkey) -> Instruction {
+    instruction(
+        multiplayer_poker::accounts::RevokeSession {
+            table: *table,
+            player: *player,
+            session: session_pda(table, player),
+        },
+        multiplayer_poker::instruction::RevokeSession {},
+    )
+}
+
+pub fn vote_run_twice(table: &Pubkey, player: &Pubkey, agree: bool) -> Instruction {
+    instruction(
+        multiplayer_poker::accounts::VoteRunTwice {
+            table: *table,
+            signer: *player,
+            player: *player,
+            session: None,
+            relayer: None,
+            sponsor: None,
+            instructions: None,
+        },
+        multiplayer_poker::instruction::VoteRunTwice { agree },
+    )
+}
+
+pub fn set_muck(table: &Pubkey, player: &Pubkey, muck: bool) -> Instruction {
+    set_muck_accounts(table, player, player, None, None, muck)
+}
+
+/// `set_muck` signed by `session_key` for `player`
+pub fn session_set_muck(table: &Pubkey, player: &Pubkey, session_key: &Pubkey, muck: bool) -> Instruction {
+    set_muck_accounts(table, session_key, player, Some(session_pda(table, player)), None, muck)
+}
+
+/// `set_muck` submitted by `relayer`, which the table's gas sponsor pays back
+pub fn sponsored_set_muck(table: &Pubkey, player: &Pubkey, relayer: &Pubkey, muck: bool) -> Instruction {
+    set_muck_accounts(table, player, player, None, Some(*relayer), muck)
+}
+
+fn set_muck_accounts(
+    table: &Pubkey,
+    signer: &Pubkey,
+    player: &Pubkey,
+    session: Option<Pubkey>,
+    relayer: Option<Pubkey>,
+    muck: bool,
+) -> Instruction {
+    instruction(
+        multiplayer_poker::accounts::SetMuck {
+            table: *table,
+            signer: *signer,
+            player: *player,
+            session,
+            relayer,
+            sponsor: relayer.map(|_| sponsor_pda(table)),
+            instructions: relayer.map(|_| INSTRUCTIONS_SYSVAR),
+        },
+        multiplayer_poker::instruction::SetMuck { muck },
+    )
+}
+
+pub fn show_or_muck(table: &Pubkey, player: &Pubkey, show: bool) -> Instruction {
+    instruction(
+        multiplayer_poker::accounts::ShowOrMuck {
+            table: *table,
+            signer: *player,
+            player: *player,
+            session: None,
+            relayer: None,
+            sponsor: None,
+            instructions: None,
+        },
+        multiplayer_poker::instruction::ShowOrMuck { show },
+    )
+}
+
+pub fn rabbit_hunt(table: &Pubkey) -> Instruction {
+    instruction(
+        multiplayer_poker::accounts::RabbitHunt { table: *table },
+        multiplayer_poker::instruction::RabbitHunt {},
+    )
+}
+
+pub fn timeout_player(table: &Pubkey) -> Instruction {
+    instruction(
+        multiplayer_poker::accounts::TimeoutPlayer { table: *table, relayer: None, sponsor: None, instructions: None },
+        multiplayer_poker::instruction::TimeoutPlayer {},
+    )
+}
+
+pub fn showdown(table: &Pubkey) -> Instruction {
+    instruction(
+        multiplayer_poker::accounts::Showdown { table: *table, relayer: None, sponsor: None, instructions: None },
+        multiplayer_poker::instruction::Showdown {},
+    )
+}
+
+/// `players` are the wallets dealt into the hand, in seat order
+pub fn distribute_pot(table: &Pubkey, players: &[Pubkey]) -> Instruction {
+    distribute_pot_with(table, None, None, players, &[])
+}
+
+/// `distribute_pot` at a table that plays for the jackpot
+pub fn distribute_jackpot_pot(table: &Pubkey, players: &[Pubkey]) -> Instruction {
+    distribute_pot_with(table, Some(jackpot_pda()), None, players, &[])
+}
+
+/// `distribute_pot` handing seats freed by leavers to the waitlist, with the
+/// wallets of the `depositors` seated, in queue order
+pub fn distribute_pot_to_waitlist(table: &Pubkey, players: &[Pubkey], depositors: &[Pubkey]) -> Instruction {
+    distribute_pot_with(table, None, Some(waitlist_pda(table)), players, depositors)
+}
+
+fn distribute_pot_with(
+    table: &Pubkey,
+    jackpot: Option<Pubkey>,
+    waitlist: Option<Pubkey>,
+    players: &[Pubkey],
+    depositors: &[Pubkey],
+) -> Instruction {
+    let mut ix = instruction(
+        multiplayer_poker::accounts::DistributePot {
+            table: *table,
+            jackpot,
+            waitlist,
+            relayer: None,
+            sponsor: None,
+            instructions: None,
+        },
+        multiplayer_poker::instruction::DistributePot {},
+    );
+    ix.accounts.extend(players.iter().map(|p| AccountMeta::new(profile_pda(p), false)));
+    ix.accounts.extend(depositors.iter().map(|p| AccountMeta::new(*p, false)));
+    ix
+}
+
+pub fn set_jackpot(admin: &Pubkey, drop_bps: u16, loser_bps: u16, winner_bps: u16, table_bps: u16) -> Instruction {
+    instruction(
+        multiplayer_poker::accounts::SetJackpot {
+            jackpot: jackpot_pda(),
+            admin: *admin,
+            program_data: program_data_address(),
+            system_program: anchor_lang::system_program::ID,
+        },
+        multiplayer_poker::instruction::SetJackpot { drop_bps, loser_bps, winner_bps, table_bps },
+    )
+}
+
+pub fn set_table_jackpot(table: &Pubkey, creator: &Pubkey, enabled: bool) -> Instruction {
+    instruction(
+        multiplayer_poker::accounts::SetTableJackpot { table: *table, creator: *creator, jackpot: jackpot_pda() },
+        multiplayer_poker::instruction::SetTableJackpot { enabled },
+    )
+}
+
+pub fn close_table(table: &Pubkey, creator: &Pubkey) -> Instruction {
+    close_table_with(table, creator, None, None, None)
+}
+
+/// `close_table` that also closes the table's waitlist, allowlist and gas sponsor
+pub fn close_table_and_pdas(table: &Pubkey, creator: &Pubkey) -> Instruction {
+    close_table_with(table, creator, Some(waitlist_pda(table)), Some(allowlist_pda(table)), Some(sponsor_pda(table)))
+}
+
+fn close_table_with(
+    table: &Pubkey,
+    creator: &Pubkey,
+    waitlist: Option<Pubkey>,
+    allowlist: Option<Pubkey>,
+    sponsor: Option<Pubkey>,
+) -> Instruction {
+    instruction(
+        multiplayer_poker::accounts::CloseTable { table: *table, creator: *creator, waitlist, allowlist, sponsor },
+        multiplayer_poker::instruction::CloseTable {},
+    )
+}
+
+pub fn force_close_table(table: &Pubkey, creator: &Pubkey, admin: &Pubkey) -> Instruction {
+    force_close_table_with(table, creator, admin, None, &[])
+}
+
+/// Force close a table with a waitlist, refunding `depositors` in queue order
+pub fn force_close_waitlisted_table(table: &Pubkey, creator: &Pubkey, admin: &Pubkey, depositors: &[Pubkey]) -> Instruction {
+    force_close_table_with(table, creator, admin, Some(waitlist_pda(table)), depositors)
+}
+
+fn force_close_table_with(
+    table: &Pubkey,
+    creator: &Pubkey,
+    admin: &Pubkey,
+    waitlist: Option<Pubkey>,
+    depositors: &[Pubkey],
+) -> Instruction {
+    let mut ix = instruction(
+        multiplayer_poker::accounts::ForceCloseTable {
+            table: *table,
+            creator: *creator,
+            admin: *admin,
+            program_data: program_data_address(),
+            waitlist,
+            allowlist: None,
+            sponsor: None,
+        },
+        multiplayer_poker::instruction::ForceCloseTable {},
+    );
+    ix.accounts.extend(depositors.iter().map(|p| AccountMeta::new(*p, false)));
+    ix
+}
+
+pub fn add_invitee(table: &Pubkey, creator: &Pubkey, invitee: &Pubkey) -> Instruction {
+    instruction(
+        multiplayer_poker::accounts::AddInvitee {
+            table: *table,
+            creator: *creator,
+            allowlist: allowlist_pda(table),
+            system_program: anchor_lang::system_program::ID,
+        },
+        multiplayer_poker::instruction::AddInvitee { invitee: *invitee },
+    )
+}
+
+pub fn remove_invitee(table: &Pubkey, creator: &Pubkey, invitee: &Pubkey) -> Instruction {
+    instruction(
+        multiplayer_poker::accounts::RemoveInvitee { table: *table, creator: *creator, allowlist: allowlist_pda(table) },
+        multiplayer_poker::instruction::RemoveInvitee { invitee: *invitee },
+    )
+}
+
+pub fn migrate_table(table: &Pubkey, payer: &Pubkey) -> Instruction {
+    instruction(
+        multiplayer_poker::accounts::MigrateTable {
+            table: *table,
+            payer: *payer,
+            system_program: anchor_lang::system_program::ID,
+        },
+        multiplayer_poker::instruction::MigrateTable {},
+    )
+}
+
+/// The custom error code of a failed single-instruction transaction
+pub fn error_code(result: Result<(), BanksClientError>) -> u32 {
+    match result.expect_err("transaction should have failed").unwrap() {
+        TransactionError::InstructionError(_, InstructionError::Custom(code)) => code,
+        other => panic!("expected a custom program error, got {other:?}"),
+    }
+}
+
+pub fn poker_error(error: PokerError) -> u32 {
+    error.into()
+}
@@ -0,0 +1,1329 @@
+mod common;
+
+use anchor_lang::error::ErrorCode;
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::{AnchorSerialize, Discriminator};
+use anchor_spl::token::spl_token;
+use common::*;
+use multiplayer_poker::error::PokerError;
+use multiplayer_poker::gate::{metadata_address, Collection, Metadata, TOKEN_METADATA_PROGRAM_ID};
+use multiplayer_poker::migration::{LegacyTable, LEGACY_TABLE_SIZE};
+use multiplayer_poker::state::{
+    ActionType, Card, GameState, Jackpot, Player, Rank, Round, SessionToken, Suit, Table, TableAccess, TableAllowlist, TableWaitlist,
+    TokenGate,
+};
+use solana_sdk::account::Account;
+use solana_sdk::signature::{Keypair, Signer};
+
+/// Check or call for whoever is to act until betting is over
+async fn check_down(harness: &mut Harness, table: &Pubkey, players: &[&Keypair]) {
+    loop {
+        let state = harness.table(table).await;
+        let Some(seat) = state.current_player() else { break };
+        let player = state.get_player(seat).unwrap();
+        let action_type = if player.bet < state.to_engine().current_bet() { ActionType::Call } else { ActionType::Check };
+        let signer = players.iter().find(|k| k.pubkey() == player.pubkey).unwrap();
+        harness.send(&[action(table, &signer.pubkey(), action_type, None)], &[signer]).await.unwrap();
+    }
+}
+
+fn chips(table: &Table) -> u64 {
+    table.players().iter().flatten().map(|p| p.stack).sum::<u64>() + table.pot
+}
+
+#[tokio::test]
+async fn full_hand_settles_and_conserves_chips() {
+    let mut harness = Harness::start().await;
+    let (creator, alice, bob) = (harness.wallet().await, harness.wallet().await, harness.wallet().await);
+    let table = harness.seated_table(&creator, &[&alice, &bob]).await;
+
+    harness.send(&[start_game(&table, &creator.pubkey())], &[&creator]).await.unwrap();
+    let state = harness.table(&table).await;
+    assert_eq!(state.game_state(), GameState::PreFlop);
+    assert_eq!(state.hand_number, 1);
+    assert_eq!(state.pot, SMALL_BLIND + BIG_BLIND);
+    assert!(state.players().iter().flatten().all(|p| p.hole_cards_committed != [0; 32]));
+
+    check_down(&mut harness, &table, &[&alice, &bob]).await;
+    let state = harness.table(&table).await;
+    assert_eq!(state.game_state(), GameState::Showdown);
+    assert_eq!(state.community_cards().iter().flatten().count(), 5);
+    assert_eq!(state.pot, 2 * BIG_BLIND);
+
+    harness.send(&[showdown(&table)], &[]).await.unwrap();
+    let players = [alice.pubkey(), bob.pubkey()];
+    harness.send(&[distribute_pot(&table, &players)], &[]).await.unwrap();
+    let state = harness.table(&table).await;
+    assert_eq!(state.game_state(), GameState::Waiting);
+    assert_eq!(state.pot, 0);
+    assert_eq!(chips(&state), 2 * BUY_IN);
+}
+
+#[tokio::test]
+async fn profiles_accumulate_hand_statistics() {
+    let mut harness = Harness::start().await;
+    let (creator, alice, bob) = (harness.wallet().await, harness.wallet().await, harness.wallet().await);
+    let table = harness.seated_table(&creator, &[&alice, &bob]).await;
+    let players = [alice.pubkey(), bob.pubkey()];
+
+    // Hand 1: the small blind limps, the big blind checks it down
+    harness.send(&[start_game(&table, &creator.pubkey())], &[&creator]).await.unwrap();
+    let state = harness.table(&table).await;
+    let small_blind = state.get_player(state.small_blind_position).unwrap().pubkey;
+    check_down(&mut harness, &table, &[&alice, &bob]).await;
+    harness.send(&[showdown(&table)], &[]).await.unwrap();
+    harness.send(&[distribute_pot(&table, &players)], &[]).await.unwrap();
+
+    let mut net = 0;
+    let mut won = 0;
+    for player in &players {
+        let profile = harness.profile(player).await;
+        assert_eq!(profile.player, *player);
+        assert_eq!(profile.hands_played, 1);
+        assert_eq!(profile.showdowns, 1);
+        assert_eq!(profile.total_wagered, BIG_BLIND);
+        assert_eq!(profile.vpip_hands, u64::from(*player == small_blind));
+        assert_eq!(profile.pfr_hands, 0);
+        net += profile.net_winnings;
+        won += profile.hands_won;
+    }
+    assert_eq!(net, 0);
+    assert!(won >= 1);
+
+    // Hand 2: the first player to act raises and the other folds
+    harness.send(&[start_game(&table, &creator.pubkey())], &[&creator]).await.unwrap();
+    let state = harness.table(&table).await;
+    let raiser = state.get_player(state.current_player().unwrap()).unwrap().pubkey;
+    let (raiser, folder) = if raiser == alice.pubkey() { (&alice, &bob) } else { (&bob, &alice) };
+    let before = (harness.profile(&raiser.pubkey()).await, harness.profile(&folder.pubkey()).await);
+    harness
+        .send(&[action(&table, &raiser.pubkey(), ActionType::Raise, Some(3 * BIG_BLIND))], &[raiser])
+        .await
+        .unwrap();
+    harness.send(&[action(&table, &folder.pubkey(), ActionType::Fold, None)], &[folder]).await.unwrap();
+    // Folding ends the hand without a showdown
+    let result = harness.send(&[showdown(&table)], &[]).await;
+    assert_eq!(error_code(result), poker_error(PokerError::InvalidGameState));
+    harness.send(&[distribute_pot(&table, &players)], &[]).await.unwrap();
+
+    let raised = harness.profile(&raiser.pubkey()).await;
+    assert_eq!(raised.vpip_hands, before.0.vpip_hands + 1);
+    assert_eq!(raised.pfr_hands, 1);
+    assert_eq!(raised.hands_won, before.0.hands_won + 1);
+    assert_eq!(raised.showdowns, 1);
+    let folded = harness.profile(&folder.pubkey()).await;
+    assert_eq!(folded.hands_played, 2);
+    assert_eq!(folded.hands_won, before.1.hands_won);
+    assert_eq!(folded.showdowns, 1);
+    assert_eq!(raised.net_winnings + folded.net_winnings, 0);
+}
+
+#[tokio::test]
+async fn reraising_after_a_call_counts_one_preflop_raise() {
+    let mut harness = Harness::start().await;
+    let (creator, alice, bob, carol) =
+        (harness.wallet().await, harness.wallet().await, harness.wallet().await, harness.wallet().await);
+    let table = harness.seated_table(&creator, &[&alice, &bob, &carol]).await;
+    harness.send(&[start_game(&table, &creator.pubkey())], &[&creator]).await.unwrap();
+
+    // The first to act raises, calls a re-raise, then raises again
+    let players = [&alice, &bob, &carol];
+    let first = harness.table(&table).await.current_player().unwrap();
+    let script = [
+        (ActionType::Raise, Some(BIG_BLIND)),
+        (ActionType::Raise, Some(BIG_BLIND)),
+        (ActionType::Raise, Some(BIG_BLIND)),
+        (ActionType::Call, None),
+        (ActionType::Raise, Some(BIG_BLIND)),
+        (ActionType::Call, None),
+        (ActionType::Raise, Some(BIG_BLIND)),
+        (ActionType::Fold, None),
+        (ActionType::Fold, None),
+    ];
+    for (action_type, amount) in script {
+        let player = players[harness.table(&table).await.current_player().unwrap() as usize];
+        harness.send(&[action(&table, &player.pubkey(), action_type, amount)], &[player]).await.unwrap();
+    }
+    assert_eq!(harness.table(&table).await.game_state(), GameState::Finished);
+    harness.send(&[distribute_pot(&table, &players.map(|p| p.pubkey()))], &[]).await.unwrap();
+
+    let profile = harness.profile(&players[first as usize].pubkey()).await;
+    assert_eq!((profile.vpip_hands, profile.pfr_hands), (1, 1));
+}
+
+#[tokio::test]
+async fn distribute_pot_requires_every_profile() {
+    let mut harness = Harness::start().await;
+    let (creator, alice, bob) = (harness.wallet().await, harness.wallet().await, harness.wallet().await);
+    let table = harness.seated_table(&creator, &[&alice, &bob]).await;
+    harness.send(&[start_game(&table, &creator.pubkey())], &[&creator]).await.unwrap();
+    check_down(&mut harness, &table, &[&alice, &bob]).await;
+    harness.send(&[showdown(&table)], &[]).await.unwrap();
+
+    let result = harness.send(&[distribute_pot(&table, &[alice.pubkey()])], &[]).await;
+    assert_eq!(error_code(result), poker_error(PokerError::InvalidProfile));
+
+    let swapped = [bob.pubkey(), alice.pubkey()];
+    let result = harness.send(&[distribute_pot(&table, &swapped)], &[]).await;
+    assert_eq!(error_code(result), poker_error(PokerError::InvalidProfile));
+}
+
+#[tokio::test]
+async fn create_table_charges_rent_to_creator() {
+    let mut harness = Harness::start().await;
+    let creator = harness.wallet().await;
+    let before = harness.lamports(&creator.pubkey()).await;
+
+    let table = harness.create_table(&creator, 7).await;
+
+    let rent = harness.ctx.banks_client.get_rent().await.unwrap();
+    let table_lamports = harness.lamports(&table).await;
+    assert_eq!(table_lamports, rent.minimum_balance(Table::MAX_SIZE));
+    // The payer covers the fee, so the creator only funds the account
+    assert_eq!(before - harness.lamports(&creator.pubkey()).await, table_lamports);
+
+    let state = harness.table(&table).await;
+    assert_eq!(state.creator, creator.pubkey());
+    assert_eq!(state.table_id, 7);
+    assert_eq!(state.game_state(), GameState::Waiting);
+}
+
+#[tokio::test]
+async fn action_after_timeout_is_rejected() {
+    let mut harness = Harness::start().await;
+    let (creator, alice, bob) = (harness.wallet().await, harness.wallet().await, harness.wallet().await);
+    let table = harness.seated_table(&creator, &[&alice, &bob]).await;
+    harness.send(&[start_game(&table, &creator.pubkey())], &[&creator]).await.unwrap();
+
+    let state = harness.table(&table).await;
+    let first = if state.current_player() == Some(0) { &alice } else { &bob };
+    harness.warp_seconds(state.action_timeout + Table::TIME_BANK as i64 + 1).await;
+
+    let result = harness.send(&[action(&table, &first.pubkey(), ActionType::Call, None)], &[first]).await;
+    assert_eq!(error_code(result), poker_error(PokerError::ActionTimeout));
+}
+
+#[tokio::test]
+async fn time_bank_covers_overtime_until_the_crank_times_out() {
+    let mut harness = Harness::start().await;
+    let (creator, alice, bob) = (harness.wallet().await, harness.wallet().await, harness.wallet().await);
+    let table = harness.seated_table(&creator, &[&alice, &bob]).await;
+    harness.send(&[start_game(&table, &creator.pubkey())], &[&creator]).await.unwrap();
+
+    // Running over the base timeout draws on the time bank
+    let state = harness.table(&table).await;
+    let first_seat = state.current_player().unwrap();
+    let first = if first_seat == 0 { &alice } else { &bob };
+    harness.warp_seconds(state.action_timeout + 10).await;
+    harness.send(&[action(&table, &first.pubkey(), ActionType::Call, None)], &[first]).await.unwrap();
+    assert_eq!(harness.table(&table).await.time_bank(first_seat), Table::TIME_BANK - 10);
+
+    // The crank waits out the base timeout and the whole bank
+    let second_seat = 1 - first_seat;
+    harness.warp_seconds(state.action_timeout + Table::TIME_BANK as i64).await;
+    let result = harness.send(&[timeout_player(&table)], &[]).await;
+    assert_eq!(error_code(result), poker_error(PokerError::ActionNotTimedOut));
+    harness.warp_seconds(1).await;
+    harness.send(&[timeout_player(&table)], &[]).await.unwrap();
+
+    // Nothing was owed, so the big blind checks and the flop comes out
+    let state = harness.table(&table).await;
+    assert_eq!(state.get_player(second_seat).unwrap().last_action, Some(ActionType::Check));
+    assert_eq!(state.time_bank(second_seat), 0);
+    assert_eq!(state.game_state(), GameState::Flop);
+}
+
+#[tokio::test]
+async fn action_within_timeout_is_accepted() {
+    let mut harness = Harness::start().await;
+    let (creator, alice, bob) = (harness.wallet().await, harness.wallet().await, harness.wallet().await);
+    let table = harness.seated_table(&creator, &[&alice, &bob]).await;
+    harness.send(&[start_game(&table, &creator.pubkey())], &[&creator]).await.unwrap();
+
+    let state = harness.table(&table).await;
+    let first = if state.current_player() == Some(0) { &alice } else { &bob };
+    harness.warp_seconds(state.action_timeout).await;
+
+    harness.send(&[action(&table, &first.pubkey(), ActionType::Call, None)], &[first]).await.unwrap();
+    let after = harness.table(&table).await;
+    assert_eq!(after.last_action_at, harness.clock().await.unix_timestamp);
+}
+
+#[tokio::test]
+async fn occupied_seat_and_unknown_player_are_rejected() {
+    let mut harness = Harness::start().await;
+    let (creator, alice, bob, carol) =
+        (harness.wallet().await, harness.wallet().await, harness.wallet().await, harness.wallet().await);
+    let table = harness.seated_table(&creator, &[&alice, &bob]).await;
+
+    let result = harness.send(&[join_table(&table, &carol.pubkey(), 1)], &[&carol]).await;
+    assert_eq!(error_code(result), poker_error(PokerError::SeatOccupied));
+
+    // Carol has a profile from another table but no seat at this one
+    let other = harness.create_table(&creator, 2).await;
+    harness.send(&[join_table(&other, &carol.pubkey(), 0)], &[&carol]).await.unwrap();
+    harness.send(&[start_game(&table, &creator.pubkey())], &[&creator]).await.unwrap();
+    let result = harness.send(&[action(&table, &carol.pubkey(), ActionType::Check, None)], &[&carol]).await;
+    assert_eq!(error_code(result), poker_error(PokerError::PlayerNotFound));
+
+    let state = harness.table(&table).await;
+    let waiting = if state.current_player() == Some(0) { &bob } else { &alice };
+    let result = harness.send(&[action(&table, &waiting.pubkey(), ActionType::Call, None)], &[waiting]).await;
+    assert_eq!(error_code(result), poker_error(PokerError::NotPlayersTurn));
+}
+
+#[tokio::test]
+async fn table_address_must_match_seeds() {
+    let mut harness = Harness::start().await;
+    let creator = harness.wallet().await;
+
+    let mut ix = create_table(&creator.pubkey(), 1, 9, TableAccess::Public, None);
+    ix.accounts[0].pubkey = table_pda(&creator.pubkey(), 2);
+    let result = harness.send(&[ix], &[&creator]).await;
+    assert_eq!(error_code(result), ErrorCode::ConstraintSeeds as u32);
+}
+
+#[tokio::test]
+async fn close_table_requires_empty_table_and_returns_rent() {
+    let mut harness = Harness::start().await;
+    let (creator, alice) = (harness.wallet().await, harness.wallet().await);
+    let table = harness.seated_table(&creator, &[&alice]).await;
+
+    let result = harness.send(&[close_table(&table, &creator.pubkey())], &[&creator]).await;
+    assert_eq!(error_code(result), poker_error(PokerError::TableNotEmpty));
+
+    harness.send(&[leave_table(&table, &alice.pubkey())], &[&alice]).await.unwrap();
+    let result = harness.send(&[close_table(&table, &alice.pubkey())], &[&alice]).await;
+    assert_eq!(error_code(result), ErrorCode::ConstraintHasOne as u32);
+
+    let rent = harness.lamports(&table).await;
+    let before = harness.lamports(&creator.pubkey()).await;
+    harness.send(&[close_table(&table, &creator.pubkey())], &[&creator]).await.unwrap();
+    assert_eq!(harness.lamports(&creator.pubkey()).await, before + rent);
+    assert!(harness.ctx.banks_client.get_account(table).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn admin_force_closes_abandoned_table() {
+    let mut harness = Harness::start().await;
+    let (creator, alice, bob) = (harness.wallet().await, harness.wallet().await, harness.wallet().await);
+    let table = harness.seated_table(&creator, &[&alice, &bob]).await;
+    let admin = harness.admin.insecure_clone();
+
+    let result = harness.send(&[force_close_table(&table, &creator.pubkey(), &admin.pubkey())], &[&admin]).await;
+    assert_eq!(error_code(result), poker_error(PokerError::TableNotAbandoned));
+
+    harness.warp_seconds(Table::ABANDON_TIMEOUT).await;
+    let result = harness.send(&[force_close_table(&table, &creator.pubkey(), &alice.pubkey())], &[&alice]).await;
+    assert_eq!(error_code(result), poker_error(PokerError::Unauthorized));
+
+    let rent = harness.lamports(&table).await;
+    let before = harness.lamports(&creator.pubkey()).await;
+    harness.send(&[force_close_table(&table, &creator.pubkey(), &admin.pubkey())], &[&admin]).await.unwrap();
+    assert_eq!(harness.lamports(&creator.pubkey()).await, before + rent);
+    assert!(harness.ctx.banks_client.get_account(table).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn start_game_requires_creator_or_seated_player() {
+    let mut harness = Harness::start().await;
+    let (creator, alice, bob, carol) =
+        (harness.wallet().await, harness.wallet().await, harness.wallet().await, harness.wallet().await);
+    let table = harness.seated_table(&creator, &[&alice]).await;
+
+    let result = harness.send(&[start_game(&table, &creator.pubkey())], &[&creator]).await;
+    assert_eq!(error_code(result), poker_error(PokerError::NotEnoughPlayers));
+
+    harness.send(&[join_table(&table, &bob.pubkey(), 1)], &[&bob]).await.unwrap();
+    let result = harness.send(&[start_game(&table, &carol.pubkey())], &[&carol]).await;
+    assert_eq!(error_code(result), poker_error(PokerError::Unauthorized));
+
+    harness.send(&[start_game(&table, &bob.pubkey())], &[&bob]).await.unwrap();
+    assert_eq!(harness.table(&table).await.game_state(), GameState::PreFlop);
+}
+
+#[tokio::test]
+async fn showdown_and_distribution_are_permissionless() {
+    let mut harness = Harness::start().await;
+    let (creator, alice, bob) = (harness.wallet().await, harness.wallet().await, harness.wallet().await);
+    let table = harness.seated_table(&creator, &[&alice, &bob]).await;
+    let players = [alice.pubkey(), bob.pubkey()];
+    harness.send(&[start_game(&table, &creator.pubkey())], &[&creator]).await.unwrap();
+
+    // Settling early is rejected no matter who asks
+    let result = harness.send(&[showdown(&table)], &[]).await;
+    assert_eq!(error_code(result), poker_error(PokerError::InvalidGameState));
+    let result = harness.send(&[distribute_pot(&table, &players)], &[]).await;
+    assert_eq!(error_code(result), poker_error(PokerError::InvalidGameState));
+
+    check_down(&mut harness, &table, &[&alice, &bob]).await;
+    // The harness payer is neither the creator nor seated
+    harness.send(&[showdown(&table), distribute_pot(&table, &players)], &[]).await.unwrap();
+    assert_eq!(harness.table(&table).await.game_state(), GameState::Waiting);
+}
+
+#[tokio::test]
+async fn table_copied_off_its_pda_is_rejected() {
+    let mut harness = Harness::start().await;
+    let (creator, alice, bob) = (harness.wallet().await, harness.wallet().await, harness.wallet().await);
+    let table = harness.seated_table(&creator, &[&alice]).await;
+
+    let (_, bump) =
+        Pubkey::find_program_address(&[Table::SEED, creator.pubkey().as_ref(), &1u64.to_le_bytes()], &multiplayer_poker::ID);
+    assert_eq!(harness.table(&table).await.bump, bump);
+
+    // Same owner, discriminator and contents, but not at the canonical address
+    let forged = Pubkey::new_unique();
+    let account = harness.ctx.banks_client.get_account(table).await.unwrap().unwrap();
+    harness.ctx.set_account(&forged, &account.into());
+
+    let result = harness.send(&[join_table(&forged, &bob.pubkey(), 1)], &[&bob]).await;
+    assert_eq!(error_code(result), ErrorCode::ConstraintSeeds as u32);
+    let result = harness.send(&[leave_table(&forged, &alice.pubkey())], &[&alice]).await;
+    assert_eq!(error_code(result), ErrorCode::ConstraintSeeds as u32);
+    let result = harness.send(&[showdown(&forged)], &[]).await;
+    assert_eq!(error_code(result), ErrorCode::ConstraintSeeds as u32);
+}
+
+/// A table as the pre-versioning program stored it, mid-hand on the flop
+fn legacy_table(creator: &Pubkey, alice: &Pubkey, bob: &Pubkey) -> LegacyTable {
+    let player = |pubkey: &Pubkey, seat: u8, stack: u64| Player {
+        pubkey: *pubkey,
+        seat,
+        stack,
+        bet: BIG_BLIND,
+        hole_cards: [Some(Card::from_u8(seat)), Some(Card::from_u8(seat + 10))],
+        hole_cards_committed: [7; 32],
+        is_all_in: false,
+        has_folded: false,
+        has_acted: true,
+        last_action: Some(ActionType::Raise),
+    };
+    let mut players = [None; 9];
+    players[0] = Some(player(alice, 0, BUY_IN - BIG_BLIND));
+    players[1] = Some(player(bob, 1, BUY_IN - BIG_BLIND - SMALL_BLIND));
+    LegacyTable {
+        creator: *creator,
+        table_id: 1,
+        small_blind: SMALL_BLIND,
+        big_blind: BIG_BLIND,
+        max_players: 9,
+        buy_in: BUY_IN,
+        game_state: GameState::Flop,
+        dealer_position: 1,
+        small_blind_position: 1,
+        big_blind_position: 0,
+        current_player: Some(0),
+        // Both flop bets plus a small blind carried over from pre-flop
+        pot: 2 * BIG_BLIND + SMALL_BLIND,
+        community_cards: [Some(Card::from_u8(40)), Some(Card::from_u8(41)), Some(Card::from_u8(42)), None, None],
+        round: Round::Flop,
+        last_raise_amount: BIG_BLIND,
+        min_raise: 2 * BIG_BLIND,
+        players,
+        player_count: 2,
+        created_at: 0,
+        last_action_at: 0,
+        action_timeout: 60,
+    }
+}
+
+/// Store `data` as a program-owned table account at `address`
+fn store_table(harness: &mut Harness, address: &Pubkey, data: Vec<u8>) {
+    let mut account = Account::new(1_000_000_000, data.len(), &multiplayer_poker::ID);
+    account.data = data;
+    harness.ctx.set_account(address, &account.into());
+}
+
+#[tokio::test]
+async fn legacy_table_is_rejected_until_migrated() {
+    let mut harness = Harness::start().await;
+    let (creator, alice, bob, carol) =
+        (harness.wallet().await, harness.wallet().await, harness.wallet().await, harness.wallet().await);
+    let table = table_pda(&creator.pubkey(), 1);
+    let mut data = Table::DISCRIMINATOR.to_vec();
+    legacy_table(&creator.pubkey(), &alice.pubkey(), &bob.pubkey()).serialize(&mut data).unwrap();
+    data.resize(LEGACY_TABLE_SIZE, 0);
+    store_table(&mut harness, &table, data);
+
+    let result = harness.send(&[join_table(&table, &carol.pubkey(), 2)], &[&carol]).await;
+    assert_eq!(error_code(result), poker_error(PokerError::UnsupportedAccountVersion));
+    let result = harness.send(&[leave_table(&table, &alice.pubkey())], &[&alice]).await;
+    assert_eq!(error_code(result), poker_error(PokerError::UnsupportedAccountVersion));
+
+    harness.send(&[migrate_table(&table, &harness.payer())], &[]).await.unwrap();
+    let account = harness.ctx.banks_client.get_account(table).await.unwrap().unwrap();
+    assert_eq!(account.data.len(), Table::MAX_SIZE);
+
+    // The hand is voided: bets and the carried-over chips go back, odd chip to the lowest seat
+    let migrated = harness.table(&table).await;
+    let (_, bump) =
+        Pubkey::find_program_address(&[Table::SEED, creator.pubkey().as_ref(), &1u64.to_le_bytes()], &multiplayer_poker::ID);
+    assert_eq!(migrated.version, Table::VERSION);
+    assert_eq!(migrated.bump, bump);
+    assert_eq!(migrated.game_state(), GameState::Waiting);
+    assert_eq!(migrated.current_player(), None);
+    assert_eq!(migrated.community_cards(), [None; 5]);
+    assert_eq!(migrated.player_count, 2);
+    assert_eq!(migrated.pot, 0);
+    let alice_seat = migrated.get_player(0).unwrap();
+    assert_eq!((alice_seat.pubkey, alice_seat.stack, alice_seat.bet), (alice.pubkey(), BUY_IN + 3, 0));
+    assert_eq!(alice_seat.hole_cards, [None; 2]);
+    assert_eq!(migrated.get_player(1).unwrap().stack, BUY_IN - 3);
+    assert_eq!(chips(&migrated), 2 * BUY_IN);
+
+    // Migrating again is a no-op, and the table is usable
+    harness.send(&[migrate_table(&table, &harness.payer())], &[]).await.unwrap();
+    harness.send(&[join_table(&table, &carol.pubkey(), 2)], &[&carol]).await.unwrap();
+    harness.send(&[start_game(&table, &creator.pubkey())], &[&creator]).await.unwrap();
+
+    // Alice and bob were seated before profiles existed, so only carol's hand is recorded
+    let players = [&alice, &bob, &carol];
+    for _ in 0..2 {
+        let player = players[harness.table(&table).await.current_player().unwrap() as usize];
+        harness.send(&[action(&table, &player.pubkey(), ActionType::Fold, None)], &[player]).await.unwrap();
+    }
+    let wallets = players.map(|p| p.pubkey());
+    let mut ix = distribute_pot(&table, &wallets);
+    let alice_profile = ix.accounts.len() - 3;
+    ix.accounts[alice_profile].pubkey = carol.pubkey();
+    let result = harness.send(&[ix], &[]).await;
+    assert_eq!(error_code(result), poker_error(PokerError::InvalidProfile));
+    harness.send(&[distribute_pot(&table, &wallets)], &[]).await.unwrap();
+    assert!(harness.ctx.banks_client.get_account(profile_pda(&alice.pubkey())).await.unwrap().is_none());
+    assert_eq!(harness.profile(&carol.pubkey()).await.hands_played, 1);
+}
+
+#[tokio::test]
+async fn table_copied_to_another_address_is_rejected() {
+    let mut harness = Harness::start().await;
+    let (creator, alice, bob) = (harness.wallet().await, harness.wallet().await, harness.wallet().await);
+    let table = harness.seated_table(&creator, &[&alice]).await;
+
+    let account = harness.ctx.banks_client.get_account(table).await.unwrap().unwrap();
+    let copy = Pubkey::new_unique();
+    store_table(&mut harness, &copy, account.data);
+    let result = harness.send(&[join_table(&copy, &bob.pubkey(), 1)], &[&bob]).await;
+    assert_eq!(error_code(result), ErrorCode::ConstraintSeeds as u32);
+    let result = harness.send(&[start_game(&copy, &alice.pubkey())], &[&alice]).await;
+    assert_eq!(error_code(result), ErrorCode::ConstraintSeeds as u32);
+}
+
+#[tokio::test]
+async fn unknown_table_version_is_rejected() {
+    let mut harness = Harness::start().await;
+    let (creator, alice, bob) = (harness.wallet().await, harness.wallet().await, harness.wallet().await);
+    let table = harness.seated_table(&creator, &[&alice]).await;
+
+    let mut account = harness.ctx.banks_client.get_account(table).await.unwrap().unwrap();
+    account.data[8] = Table::VERSION + 1;
+    store_table(&mut harness, &table, account.data);
+
+    let result = harness.send(&[join_table(&table, &bob.pubkey(), 1)], &[&bob]).await;
+    assert_eq!(error_code(result), poker_error(PokerError::UnsupportedAccountVersion));
+    let result = harness.send(&[migrate_table(&table, &harness.payer())], &[]).await;
+    assert_eq!(error_code(result), poker_error(PokerError::UnsupportedAccountVersion));
+}
+
+#[tokio::test]
+async fn allowlist_table_admits_only_invitees() {
+    let mut harness = Harness::start().await;
+    let (creator, alice, bob) = (harness.wallet().await, harness.wallet().await, harness.wallet().await);
+    let ix = create_table(&creator.pubkey(), 1, 9, TableAccess::Allowlist, None);
+    harness.send(&[ix], &[&creator]).await.unwrap();
+    let table = table_pda(&creator.pubkey(), 1);
+
+    let result = harness.send(&[join_table(&table, &alice.pubkey(), 1)], &[&alice]).await;
+    assert_eq!(error_code(result), poker_error(PokerError::NotInvited));
+    let result = harness.send(&[add_invitee(&table, &alice.pubkey(), &alice.pubkey())], &[&alice]).await;
+    assert_eq!(error_code(result), ErrorCode::ConstraintHasOne as u32);
+
+    harness.send(&[add_invitee(&table, &creator.pubkey(), &alice.pubkey())], &[&creator]).await.unwrap();
+    harness.send(&[add_invitee(&table, &creator.pubkey(), &bob.pubkey())], &[&creator]).await.unwrap();
+    harness.send(&[remove_invitee(&table, &creator.pubkey(), &bob.pubkey())], &[&creator]).await.unwrap();
+    let allowlist: TableAllowlist = harness.account(&allowlist_pda(&table)).await;
+    assert_eq!(allowlist.invitees, vec![alice.pubkey()]);
+    let result = harness.send(&[remove_invitee(&table, &creator.pubkey(), &bob.pubkey())], &[&creator]).await;
+    assert_eq!(error_code(result), poker_error(PokerError::NotInvited));
+
+    let result = harness.send(&[join_private_table(&table, &bob.pubkey(), 1, true, None)], &[&bob]).await;
+    assert_eq!(error_code(result), poker_error(PokerError::NotInvited));
+    harness.send(&[join_private_table(&table, &alice.pubkey(), 1, true, None)], &[&alice]).await.unwrap();
+    // The creator needs no invitation
+    harness.send(&[join_table(&table, &creator.pubkey(), 0)], &[&creator]).await.unwrap();
+
+    // The list is frozen once a hand is running
+    harness.send(&[start_game(&table, &creator.pubkey())], &[&creator]).await.unwrap();
+    let result = harness.send(&[add_invitee(&table, &creator.pubkey(), &bob.pubkey())], &[&creator]).await;
+    assert_eq!(error_code(result), poker_error(PokerError::GameInProgress));
+    let result = harness.send(&[remove_invitee(&table, &creator.pubkey(), &alice.pubkey())], &[&creator]).await;
+    assert_eq!(error_code(result), poker_error(PokerError::GameInProgress));
+}
+
+#[tokio::test]
+async fn password_table_requires_preimage() {
+    let mut harness = Harness::start().await;
+    let (creator, alice) = (harness.wallet().await, harness.wallet().await);
+    let table = table_pda(&creator.pubkey(), 1);
+    let hash = Table::password_commitment(&table, b"hunter2");
+    let ix = create_table(&creator.pubkey(), 1, 9, TableAccess::Password { hash }, None);
+    harness.send(&[ix], &[&creator]).await.unwrap();
+
+    let result = harness.send(&[join_table(&table, &alice.pubkey(), 0)], &[&alice]).await;
+    assert_eq!(error_code(result), poker_error(PokerError::InvalidPassword));
+    let result = harness.send(&[join_private_table(&table, &alice.pubkey(), 0, false, Some(b"hunter3"))], &[&alice]).await;
+    assert_eq!(error_code(result), poker_error(PokerError::InvalidPassword));
+    // Invitations are for allowlist tables only
+    let result = harness.send(&[add_invitee(&table, &creator.pubkey(), &alice.pubkey())], &[&creator]).await;
+    assert_eq!(error_code(result), poker_error(PokerError::NotAllowlistTable));
+
+    harness.send(&[join_private_table(&table, &alice.pubkey(), 0, false, Some(b"hunter2"))], &[&alice]).await.unwrap();
+    assert_eq!(harness.table(&table).await.player_count, 1);
+}
+
+/// Store an initialized SPL token account and return its address
+fn store_token_account(harness: &mut Harness, owner: &Pubkey, mint: &Pubkey, amount: u64) -> Pubkey {
+    let address = Pubkey::new_unique();
+    let state = spl_token::state::Account {
+        mint: *mint,
+        owner: *owner,
+        amount,
+        state: spl_token::state::AccountState::Initialized,
+        ..Default::default()
+    };
+    let mut account = Account::new(1_000_000_000, spl_token::state::Account::LEN, &spl_token::ID);
+    state.pack_into_slice(&mut account.data);
+    harness.ctx.set_account(&address, &account.into());
+    address
+}
+
+/// Store Metaplex metadata for `mint` naming `collection`
+fn store_metadata(harness: &mut Harness, mint: &Pubkey, collection: Option<Collection>) -> Pubkey {
+    let metadata = Metadata {
+        key: 4,
+        update_authority: Pubkey::new_unique(),
+        mint: *mint,
+        name: "Chip #1".to_string(),
+        symbol: "CHIP".to_string(),
+        uri: String::new(),
+        seller_fee_basis_points: 0,
+        creators: None,
+        primary_sale_happened: false,
+        is_mutable: true,
+        edition_nonce: Some(255),
+        token_standard: Some(0),
+        collection,
+    };
+    let address = metadata_address(mint);
+    let mut account = Account::new(1_000_000_000, 0, &TOKEN_METADATA_PROGRAM_ID);
+    metadata.serialize(&mut account.data).unwrap();
+    // Metaplex pads metadata accounts with trailing fields this program ignores
+    account.data.resize(account.data.len() + 64, 0);
+    harness.ctx.set_account(&address, &account.into());
+    address
+}
+
+#[tokio::test]
+async fn token_gate_requires_minimum_balance_of_mint() {
+    let mut harness = Harness::start().await;
+    let (creator, alice, bob) = (harness.wallet().await, harness.wallet().await, harness.wallet().await);
+    let (mint, other_mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let gate = TokenGate::Token { mint, min_balance: 100 };
+    harness.send(&[create_table(&creator.pubkey(), 1, 9, TableAccess::Public, Some(gate))], &[&creator]).await.unwrap();
+    let table = table_pda(&creator.pubkey(), 1);
+    assert_eq!(harness.table(&table).await.gate(), Some(gate));
+
+    let short = store_token_account(&mut harness, &alice.pubkey(), &mint, 99);
+    let wrong_mint = store_token_account(&mut harness, &alice.pubkey(), &other_mint, 100);
+    let bobs = store_token_account(&mut harness, &bob.pubkey(), &mint, 100);
+    let enough = store_token_account(&mut harness, &alice.pubkey(), &mint, 100);
+
+    let result = harness.send(&[join_table(&table, &alice.pubkey(), 1)], &[&alice]).await;
+    assert_eq!(error_code(result), poker_error(PokerError::TokenGateNotMet));
+    for token_account in [short, wrong_mint, bobs] {
+        let result = harness.send(&[join_gated_table(&table, &alice.pubkey(), 1, &token_account, None)], &[&alice]).await;
+        assert_eq!(error_code(result), poker_error(PokerError::TokenGateNotMet));
+    }
+
+    harness.send(&[join_gated_table(&table, &alice.pubkey(), 1, &enough, None)], &[&alice]).await.unwrap();
+    harness.send(&[join_table(&table, &creator.pubkey(), 0)], &[&creator]).await.unwrap();
+}
+
+#[tokio::test]
+async fn collection_gate_requires_verified_nft() {
+    let mut harness = Harness::start().await;
+    let (creator, alice, bob) = (harness.wallet().await, harness.wallet().await, harness.wallet().await);
+    let collection = Pubkey::new_unique();
+    let gate = TokenGate::Collection { collection };
+    harness.send(&[create_table(&creator.pubkey(), 1, 9, TableAccess::Public, Some(gate))], &[&creator]).await.unwrap();
+    let table = table_pda(&creator.pubkey(), 1);
+
+    let (nft, unverified, stranger) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+    let nft_metadata = store_metadata(&mut harness, &nft, Some(Collection { verified: true, key: collection }));
+    let unverified_metadata = store_metadata(&mut harness, &unverified, Some(Collection { verified: false, key: collection }));
+    let stranger_metadata = store_metadata(&mut harness, &stranger, None);
+    let alices = store_token_account(&mut harness, &alice.pubkey(), &nft, 1);
+    let bobs_unverified = store_token_account(&mut harness, &bob.pubkey(), &unverified, 1);
+    let bobs_stranger = store_token_account(&mut harness, &bob.pubkey(), &stranger, 1);
+
+    // Metadata of another mint, a missing collection and an unverified one all fail
+    for (token_account, metadata) in
+        [(bobs_stranger, Some(nft_metadata)), (bobs_stranger, Some(stranger_metadata)), (bobs_unverified, Some(unverified_metadata)), (bobs_stranger, None)]
+    {
+        let result = harness.send(&[join_gated_table(&table, &bob.pubkey(), 1, &token_account, metadata)], &[&bob]).await;
+        assert_eq!(error_code(result), poker_error(PokerError::TokenGateNotMet));
+    }
+
+    harness.send(&[join_gated_table(&table, &alice.pubkey(), 1, &alices, Some(nft_metadata))], &[&alice]).await.unwrap();
+}
+
+/// A two-seat table with `players` seated
+async fn full_table(harness: &mut Harness, creator: &Keypair, players: [&Keypair; 2]) -> Pubkey {
+    harness.send(&[create_table(&creator.pubkey(), 1, 2, TableAccess::Public, None)], &[creator]).await.unwrap();
+    let table = table_pda(&creator.pubkey(), 1);
+    for (seat, player) in players.iter().enumerate() {
+        harness.send(&[join_table(&table, &player.pubkey(), seat as u8)], &[player]).await.unwrap();
+    }
+    table
+}
+
+#[tokio::test]
+async fn freed_seat_goes_to_waitlist() {
+    let mut harness = Harness::start().await;
+    let (creator, alice, bob, carol, dave) =
+        (harness.wallet().await, harness.wallet().await, harness.wallet().await, harness.wallet().await, harness.wallet().await);
+    harness.send(&[create_table(&creator.pubkey(), 1, 2, TableAccess::Public, None)], &[&creator]).await.unwrap();
+    let table = table_pda(&creator.pubkey(), 1);
+    harness.send(&[join_table(&table, &alice.pubkey(), 0)], &[&alice]).await.unwrap();
+    let result = harness.send(&[join_waitlist(&table, &carol.pubkey(), false)], &[&carol]).await;
+    assert_eq!(error_code(result), poker_error(PokerError::TableNotFull));
+    // Seats past max_players don't exist, even with one free
+    let result = harness.send(&[join_table(&table, &bob.pubkey(), 2)], &[&bob]).await;
+    assert_eq!(error_code(result), poker_error(PokerError::InvalidSeat));
+    harness.send(&[join_table(&table, &bob.pubkey(), 1)], &[&bob]).await.unwrap();
+
+    let result = harness.send(&[join_table(&table, &carol.pubkey(), 0)], &[&carol]).await;
+    assert_eq!(error_code(result), poker_error(PokerError::TableFull));
+    harness.send(&[join_waitlist(&table, &carol.pubkey(), false)], &[&carol]).await.unwrap();
+    let before = harness.lamports(&waitlist_pda(&table)).await;
+    harness.send(&[join_waitlist(&table, &dave.pubkey(), true)], &[&dave]).await.unwrap();
+    assert_eq!(harness.lamports(&waitlist_pda(&table)).await, before + BUY_IN);
+    let result = harness.send(&[join_waitlist(&table, &dave.pubkey(), true)], &[&dave]).await;
+    assert_eq!(error_code(result), poker_error(PokerError::AlreadyWaitlisted));
+
+    // The seat can't be released past the queue, and goes to carol on hold
+    let result = harness.send(&[leave_table(&table, &alice.pubkey())], &[&alice]).await;
+    assert_eq!(error_code(result), poker_error(PokerError::WaitlistMismatch));
+    harness.send(&[leave_to_waitlist(&table, &alice.pubkey(), &carol.pubkey())], &[&alice]).await.unwrap();
+    let state = harness.table(&table).await;
+    let now = harness.clock().await.unix_timestamp;
+    assert_eq!(state.reservation(0, now), Some(carol.pubkey()));
+    assert_eq!(state.waitlist_len, 1);
+
+    let result = harness.send(&[join_table(&table, &dave.pubkey(), 0)], &[&dave]).await;
+    assert_eq!(error_code(result), poker_error(PokerError::SeatReserved));
+    harness.send(&[join_table(&table, &carol.pubkey(), 0)], &[&carol]).await.unwrap();
+
+    // Dave deposited, so they are seated as soon as bob leaves and get the deposit back
+    let (dave_before, waitlist_before) = (harness.lamports(&dave.pubkey()).await, harness.lamports(&waitlist_pda(&table)).await);
+    let result = harness.send(&[leave_to_waitlist(&table, &bob.pubkey(), &carol.pubkey())], &[&bob]).await;
+    assert_eq!(error_code(result), poker_error(PokerError::WaitlistMismatch));
+    harness.send(&[leave_to_waitlist(&table, &bob.pubkey(), &dave.pubkey())], &[&bob]).await.unwrap();
+    let state = harness.table(&table).await;
+    assert_eq!(state.get_player(1).unwrap().pubkey, dave.pubkey());
+    assert_eq!(state.get_player(1).unwrap().stack, BUY_IN);
+    assert_eq!(state.waitlist_len, 0);
+    assert_eq!(harness.lamports(&dave.pubkey()).await, dave_before + BUY_IN);
+    assert_eq!(harness.lamports(&waitlist_pda(&table)).await, waitlist_before - BUY_IN);
+    let waitlist: TableWaitlist = harness.account(&waitlist_pda(&table)).await;
+    assert!(waitlist.entries.is_empty());
+}
+
+#[tokio::test]
+async fn lapsed_reservation_frees_the_seat_for_the_waitlist() {
+    let mut harness = Harness::start().await;
+    let (creator, alice, bob, carol, dave, erin, frank) = (
+        harness.wallet().await,
+        harness.wallet().await,
+        harness.wallet().await,
+        harness.wallet().await,
+        harness.wallet().await,
+        harness.wallet().await,
+        harness.wallet().await,
+    );
+    let table = full_table(&mut harness, &creator, [&alice, &bob]).await;
+    for player in [&carol, &dave, &erin, &frank] {
+        harness.send(&[join_waitlist(&table, &player.pubkey(), player == &erin)], &[player]).await.unwrap();
+    }
+
+    // Leaving the queue returns the deposit
+    let before = harness.lamports(&erin.pubkey()).await;
+    harness.send(&[leave_waitlist(&table, &erin.pubkey())], &[&erin]).await.unwrap();
+    assert_eq!(harness.lamports(&erin.pubkey()).await, before + BUY_IN);
+    let result = harness.send(&[leave_waitlist(&table, &erin.pubkey())], &[&erin]).await;
+    assert_eq!(error_code(result), poker_error(PokerError::NotWaitlisted));
+
+    harness.send(&[leave_to_waitlist(&table, &alice.pubkey(), &carol.pubkey())], &[&alice]).await.unwrap();
+    harness.warp_seconds(Table::RESERVATION_TIMEOUT).await;
+
+    // Carol let the hold lapse; queued players still come ahead of newcomers,
+    // but dave at the head doesn't have to be the one to take the seat
+    let result = harness.send(&[join_table(&table, &erin.pubkey(), 0)], &[&erin]).await;
+    assert_eq!(error_code(result), poker_error(PokerError::WaitlistAhead));
+    let result = harness.send(&[join_table(&table, &carol.pubkey(), 0)], &[&carol]).await;
+    assert_eq!(error_code(result), poker_error(PokerError::WaitlistAhead));
+    harness.send(&[join_from_waitlist(&table, &frank.pubkey(), 0)], &[&frank]).await.unwrap();
+    assert_eq!(harness.table(&table).await.find_seat(&frank.pubkey()), Some(0));
+    let waitlist: TableWaitlist = harness.account(&waitlist_pda(&table)).await;
+    assert_eq!(waitlist.position(&dave.pubkey()), Some(0));
+    assert_eq!(harness.table(&table).await.waitlist_len, 1);
+}
+
+#[tokio::test]
+async fn close_table_returns_the_rent_of_its_other_accounts() {
+    let mut harness = Harness::start().await;
+    let (creator, alice, bob) = (harness.wallet().await, harness.wallet().await, harness.wallet().await);
+    harness.send(&[create_table(&creator.pubkey(), 1, 2, TableAccess::Allowlist, None)], &[&creator]).await.unwrap();
+    let table = table_pda(&creator.pubkey(), 1);
+    for (seat, player) in [&alice, &bob].iter().enumerate() {
+        harness.send(&[add_invitee(&table, &creator.pubkey(), &player.pubkey())], &[&creator]).await.unwrap();
+        harness.send(&[join_private_table(&table, &player.pubkey(), seat as u8, true, None)], &[player]).await.unwrap();
+    }
+    // An emptied waitlist and a sponsor with funds left over
+    harness.send(&[join_waitlist(&table, &creator.pubkey(), false)], &[&creator]).await.unwrap();
+    harness.send(&[leave_waitlist(&table, &creator.pubkey())], &[&creator]).await.unwrap();
+    harness.send(&[set_gas_sponsor(&table, &creator.pubkey(), &alice.pubkey(), 5_000, 20_000)], &[&creator]).await.unwrap();
+    for player in [&alice, &bob] {
+        harness.send(&[leave_table(&table, &player.pubkey())], &[player]).await.unwrap();
+    }
+
+    let accounts = [table, waitlist_pda(&table), allowlist_pda(&table), sponsor_pda(&table)];
+    let mut rent = 0;
+    for account in &accounts {
+        rent += harness.lamports(account).await;
+    }
+    let before = harness.lamports(&creator.pubkey()).await;
+    harness.send(&[close_table_and_pdas(&table, &creator.pubkey())], &[&creator]).await.unwrap();
+    assert_eq!(harness.lamports(&creator.pubkey()).await, before + rent);
+    for account in accounts {
+        assert!(harness.ctx.banks_client.get_account(account).await.unwrap().is_none());
+    }
+}
+
+#[tokio::test]
+async fn waitlisted_deposits_keep_a_table_open_until_refunded() {
+    let mut harness = Harness::start().await;
+    let (creator, alice, bob, carol, dave, erin) = (
+        harness.wallet().await,
+        harness.wallet().await,
+        harness.wallet().await,
+        harness.wallet().await,
+        harness.wallet().await,
+        harness.wallet().await,
+    );
+    let table = full_table(&mut harness, &creator, [&alice, &bob]).await;
+    for player in [&carol, &dave, &erin] {
+        harness.send(&[join_waitlist(&table, &player.pubkey(), player == &erin)], &[player]).await.unwrap();
+    }
+
+    // Both seats go to holds, leaving an empty table with erin's deposit queued
+    harness.send(&[leave_to_waitlist(&table, &alice.pubkey(), &carol.pubkey())], &[&alice]).await.unwrap();
+    harness.send(&[leave_to_waitlist(&table, &bob.pubkey(), &dave.pubkey())], &[&bob]).await.unwrap();
+    let state = harness.table(&table).await;
+    assert_eq!((state.player_count, state.waitlist_len), (0, 1));
+    let result = harness.send(&[close_table(&table, &creator.pubkey())], &[&creator]).await;
+    assert_eq!(error_code(result), poker_error(PokerError::WaitlistNotEmpty));
+
+    // Force closing refunds the deposit and closes the waitlist with the table
+    let admin = harness.admin.insecure_clone();
+    harness.warp_seconds(Table::ABANDON_TIMEOUT).await;
+    let result = harness.send(&[force_close_table(&table, &creator.pubkey(), &admin.pubkey())], &[&admin]).await;
+    assert_eq!(error_code(result), poker_error(PokerError::WaitlistMismatch));
+    let ix = force_close_waitlisted_table(&table, &creator.pubkey(), &admin.pubkey(), &[dave.pubkey()]);
+    let result = harness.send(&[ix], &[&admin]).await;
+    assert_eq!(error_code(result), poker_error(PokerError::WaitlistMismatch));
+
+    let erin_before = harness.lamports(&erin.pubkey()).await;
+    let creator_before = harness.lamports(&creator.pubkey()).await;
+    let rent = harness.lamports(&table).await + harness.lamports(&waitlist_pda(&table)).await - BUY_IN;
+    let ix = force_close_waitlisted_table(&table, &creator.pubkey(), &admin.pubkey(), &[erin.pubkey()]);
+    harness.send(&[ix], &[&admin]).await.unwrap();
+    assert_eq!(harness.lamports(&erin.pubkey()).await, erin_before + BUY_IN);
+    assert_eq!(harness.lamports(&creator.pubkey()).await, creator_before + rent);
+    assert!(harness.ctx.banks_client.get_account(waitlist_pda(&table)).await.unwrap().is_none());
+    assert!(harness.ctx.banks_client.get_account(table).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn any_seat_skips_taken_and_held_seats() {
+    let mut harness = Harness::start().await;
+    let (creator, alice, bob, carol, dave) =
+        (harness.wallet().await, harness.wallet().await, harness.wallet().await, harness.wallet().await, harness.wallet().await);
+    let table = harness.seated_table(&creator, &[&alice]).await;
+
+    // Bob holds seat 1 while funding, so carol's any-seat join lands on seat 2
+    harness.send(&[reserve_seat(&table, &bob.pubkey(), None)], &[&bob]).await.unwrap();
+    let now = harness.clock().await.unix_timestamp;
+    assert_eq!(harness.table(&table).await.reservation(1, now), Some(bob.pubkey()));
+    let result = harness.send(&[join_table(&table, &carol.pubkey(), 1)], &[&carol]).await;
+    assert_eq!(error_code(result), poker_error(PokerError::SeatReserved));
+    let result = harness.send(&[reserve_seat(&table, &carol.pubkey(), Some(1))], &[&carol]).await;
+    assert_eq!(error_code(result), poker_error(PokerError::SeatReserved));
+    let result = harness.send(&[reserve_seat(&table, &carol.pubkey(), Some(0))], &[&carol]).await;
+    assert_eq!(error_code(result), poker_error(PokerError::SeatOccupied));
+    harness.send(&[join_any_seat(&table, &carol.pubkey())], &[&carol]).await.unwrap();
+    assert_eq!(harness.table(&table).await.find_seat(&carol.pubkey()), Some(2));
+
+    // The holder's any-seat join takes the held seat
+    harness.send(&[join_any_seat(&table, &bob.pubkey())], &[&bob]).await.unwrap();
+    let state = harness.table(&table).await;
+    assert_eq!(state.find_seat(&bob.pubkey()), Some(1));
+    assert_eq!(state.reservation(1, now), None);
+    let result = harness.send(&[reserve_seat(&table, &bob.pubkey(), None)], &[&bob]).await;
+    assert_eq!(error_code(result), poker_error(PokerError::AlreadySeated));
+    let result = harness.send(&[join_table(&table, &bob.pubkey(), 3)], &[&bob]).await;
+    assert_eq!(error_code(result), poker_error(PokerError::AlreadySeated));
+
+    // Holds lapse on their own, and can't be renewed back to back
+    harness.send(&[reserve_seat(&table, &dave.pubkey(), Some(5))], &[&dave]).await.unwrap();
+    let result = harness.send(&[reserve_seat(&table, &dave.pubkey(), Some(6))], &[&dave]).await;
+    assert_eq!(error_code(result), poker_error(PokerError::ReservationCooldown));
+    harness.warp_seconds(Table::RESERVATION_TIMEOUT).await;
+    let result = harness.send(&[reserve_seat(&table, &dave.pubkey(), Some(5))], &[&dave]).await;
+    assert_eq!(error_code(result), poker_error(PokerError::ReservationCooldown));
+    let erin = harness.wallet().await;
+    harness.send(&[join_table(&table, &erin.pubkey(), 5)], &[&erin]).await.unwrap();
+    harness.warp_seconds(Table::RESERVATION_TIMEOUT).await;
+    harness.send(&[reserve_seat(&table, &dave.pubkey(), None)], &[&dave]).await.unwrap();
+}
+
+#[tokio::test]
+async fn leaving_mid_hand_folds_and_cashes_out_at_settlement() {
+    let mut harness = Harness::start().await;
+    let (creator, alice, bob, carol) =
+        (harness.wallet().await, harness.wallet().await, harness.wallet().await, harness.wallet().await);
+    let table = harness.seated_table(&creator, &[&alice, &bob, &carol]).await;
+    harness.send(&[start_game(&table, &creator.pubkey())], &[&creator]).await.unwrap();
+
+    // Someone other than the player to act walks away
+    let state = harness.table(&table).await;
+    let to_act = state.current_player().unwrap();
+    let leaver = [&alice, &bob, &carol]
+        .into_iter()
+        .find(|k| state.find_seat(&k.pubkey()) != Some(to_act))
+        .unwrap();
+    let seat = state.find_seat(&leaver.pubkey()).unwrap();
+    harness.send(&[leave_table(&table, &leaver.pubkey())], &[leaver]).await.unwrap();
+    let state = harness.table(&table).await;
+    assert!(state.get_player(seat).unwrap().has_folded);
+    assert!(state.is_leaving(seat));
+    assert_eq!(state.current_player(), Some(to_act));
+    assert_eq!(state.player_count, 3);
+    let result = harness.send(&[leave_table(&table, &leaver.pubkey())], &[leaver]).await;
+    assert_eq!(error_code(result), poker_error(PokerError::AlreadyLeaving));
+
+    check_down(&mut harness, &table, &[&alice, &bob, &carol]).await;
+    harness.send(&[showdown(&table)], &[]).await.unwrap();
+    let state = harness.table(&table).await;
+    let cash_out = state.get_player(seat).unwrap().stack;
+    let players: Vec<Pubkey> = state.players().iter().flatten().map(|p| p.pubkey).collect();
+    harness.send(&[distribute_pot(&table, &players)], &[]).await.unwrap();
+
+    // The seat frees up once the hand is paid out
+    let state = harness.table(&table).await;
+    assert_eq!(state.game_state(), GameState::Waiting);
+    assert_eq!(state.find_seat(&leaver.pubkey()), None);
+    assert!(!state.is_leaving(seat));
+    assert_eq!(state.player_count, 2);
+    assert_eq!(chips(&state) + cash_out, 3 * BUY_IN);
+}
+
+#[tokio::test]
+async fn session_key_acts_for_player_until_revoked() {
+    let mut harness = Harness::start().await;
+    let (creator, alice, bob) = (harness.wallet().await, harness.wallet().await, harness.wallet().await);
+    let table = harness.seated_table(&creator, &[&alice, &bob]).await;
+    harness.send(&[start_game(&table, &creator.pubkey())], &[&creator]).await.unwrap();
+    let state = harness.table(&table).await;
+    let first = if state.current_player() == Some(0) { &alice } else { &bob };
+    let session_key = Keypair::new();
+
+    // Without a session the key can't act
+    let result = harness.send(&[session_action(&table, &first.pubkey(), &session_key.pubkey(), ActionType::Call)], &[&session_key]).await;
+    assert_eq!(error_code(result), ErrorCode::AccountNotInitialized as u32);
+
+    let now = harness.clock().await.unix_timestamp;
+    let too_long = now + SessionToken::MAX_DURATION + 1;
+    let result = harness.send(&[create_session(&table, &first.pubkey(), &session_key.pubkey(), too_long)], &[first]).await;
+    assert_eq!(error_code(result), poker_error(PokerError::InvalidSessionExpiry));
+    harness.send(&[create_session(&table, &first.pubkey(), &session_key.pubkey(), now + 600)], &[first]).await.unwrap();
+    let session: SessionToken = harness.account(&session_pda(&table, &first.pubkey())).await;
+    assert_eq!(session.session_key, session_key.pubkey());
+
+    // Another key can't borrow the session
+    let stranger = Keypair::new();
+    let result = harness.send(&[session_action(&table, &first.pubkey(), &stranger.pubkey(), ActionType::Call)], &[&stranger]).await;
+    assert_eq!(error_code(result), poker_error(PokerError::InvalidSessionKey));
+    harness.send(&[session_action(&table, &first.pubkey(), &session_key.pubkey(), ActionType::Call)], &[&session_key]).await.unwrap();
+    assert_eq!(harness.table(&table).await.to_engine().current_bet(), BIG_BLIND);
+
+    // Table preferences can be set through the session too
+    let result = harness.send(&[session_set_muck(&table, &first.pubkey(), &stranger.pubkey(), true)], &[&stranger]).await;
+    assert_eq!(error_code(result), poker_error(PokerError::InvalidSessionKey));
+    harness.send(&[session_set_muck(&table, &first.pubkey(), &session_key.pubkey(), true)], &[&session_key]).await.unwrap();
+    let state = harness.table(&table).await;
+    assert!(state.mucks(state.find_seat(&first.pubkey()).unwrap()));
+
+    // Revoking returns the rent and ends the session
+    harness.send(&[revoke_session(&table, &first.pubkey())], &[first]).await.unwrap();
+    assert!(harness.ctx.banks_client.get_account(session_pda(&table, &first.pubkey())).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn expired_session_is_rejected() {
+    let mut harness = Harness::start().await;
+    let (creator, alice, bob) = (harness.wallet().await, harness.wallet().await, harness.wallet().await);
+    let table = harness.seated_table(&creator, &[&alice, &bob]).await;
+    let session_key = Keypair::new();
+    let now = harness.clock().await.unix_timestamp;
+    harness.send(&[create_session(&table, &alice.pubkey(), &session_key.pubkey(), now + 30)], &[&alice]).await.unwrap();
+    harness.send(&[start_game(&table, &creator.pubkey())], &[&creator]).await.unwrap();
+    if harness.table(&table).await.current_player() != Some(0) {
+        harness.send(&[action(&table, &bob.pubkey(), ActionType::Call, None)], &[&bob]).await.unwrap();
+    }
+
+    harness.warp_seconds(31).await;
+    let result = harness.send(&[session_action(&table, &alice.pubkey(), &session_key.pubkey(), ActionType::Check)], &[&session_key]).await;
+    assert_eq!(error_code(result), poker_error(PokerError::SessionExpired));
+}
+
+#[tokio::test]
+async fn gas_sponsor_reimburses_its_relayer_while_funded() {
+    let mut harness = Harness::start().await;
+    let (creator, alice, bob, relayer) =
+        (harness.wallet().await, harness.wallet().await, harness.wallet().await, harness.wallet().await);
+    let table = harness.seated_table(&creator, &[&alice, &bob]).await;
+
+    let result = harness.send(&[set_gas_sponsor(&table, &alice.pubkey(), &alice.pubkey(), 5_000, 0)], &[&alice]).await;
+    assert_eq!(error_code(result), ErrorCode::ConstraintHasOne as u32);
+    harness.send(&[set_gas_sponsor(&table, &creator.pubkey(), &relayer.pubkey(), 5_000, 25_000)], &[&creator]).await.unwrap();
+
+    // Cranks are sponsored too
+    let before = harness.lamports(&relayer.pubkey()).await;
+    harness.send(&[sponsored_start_game(&table, &creator.pubkey(), &relayer.pubkey())], &[&creator, &relayer]).await.unwrap();
+    assert_eq!(harness.lamports(&relayer.pubkey()).await, before + 5_000);
+
+    let seats = |state: &Table| {
+        let seat = state.current_player().unwrap();
+        if seat == 0 { (&alice, &bob) } else { (&bob, &alice) }
+    };
+    let (first, second) = seats(&harness.table(&table).await);
+
+    // Only the sponsor's relayer is paid back
+    let stranger = harness.wallet().await;
+    let result = harness.send(&[sponsored_action(&table, &first.pubkey(), &stranger.pubkey(), ActionType::Call)], &[first, &stranger]).await;
+    assert_eq!(error_code(result), poker_error(PokerError::InvalidRelayer));
+
+    harness.send(&[sponsored_action(&table, &first.pubkey(), &relayer.pubkey(), ActionType::Call)], &[first, &relayer]).await.unwrap();
+    harness.send(&[sponsored_action(&table, &second.pubkey(), &relayer.pubkey(), ActionType::Check)], &[second, &relayer]).await.unwrap();
+    assert_eq!(harness.lamports(&relayer.pubkey()).await, before + 15_000);
+
+    // Table preferences are gameplay too, but packing them pays once per transaction
+    let mut ix = sponsored_set_muck(&table, &first.pubkey(), &relayer.pubkey(), true);
+    let last = ix.accounts.len() - 1;
+    ix.accounts[last].pubkey = multiplayer_poker::ID;
+    let result = harness.send(&[ix], &[first, &relayer]).await;
+    assert_eq!(error_code(result), poker_error(PokerError::InstructionsSysvarRequired));
+    let packed: Vec<_> = [true, false, true]
+        .into_iter()
+        .map(|muck| sponsored_set_muck(&table, &first.pubkey(), &relayer.pubkey(), muck))
+        .collect();
+    harness.send(&packed, &[first, &relayer]).await.unwrap();
+    assert_eq!(harness.lamports(&relayer.pubkey()).await, before + 20_000);
+
+    // Once the deposit is spent, play goes on unpaid
+    let (next, _) = seats(&harness.table(&table).await);
+    harness.send(&[sponsored_action(&table, &next.pubkey(), &relayer.pubkey(), ActionType::Check)], &[next, &relayer]).await.unwrap();
+    let (next, _) = seats(&harness.table(&table).await);
+    harness.send(&[sponsored_action(&table, &next.pubkey(), &relayer.pubkey(), ActionType::Check)], &[next, &relayer]).await.unwrap();
+    assert_eq!(harness.lamports(&relayer.pubkey()).await, before + 25_000);
+
+    harness.send(&[close_gas_sponsor(&table, &creator.pubkey())], &[&creator]).await.unwrap();
+    assert!(harness.ctx.banks_client.get_account(sponsor_pda(&table)).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn all_in_runs_twice_when_everyone_agrees() {
+    let mut harness = Harness::start().await;
+    let (creator, alice, bob) = (harness.wallet().await, harness.wallet().await, harness.wallet().await);
+    let table = harness.seated_table(&creator, &[&alice, &bob]).await;
+    let result = harness.send(&[vote_run_twice(&table, &alice.pubkey(), true)], &[&alice]).await;
+    assert_eq!(error_code(result), poker_error(PokerError::InvalidGameState));
+    harness.send(&[start_game(&table, &creator.pubkey())], &[&creator]).await.unwrap();
+
+    let result = harness.send(&[vote_run_twice(&table, &creator.pubkey(), true)], &[&creator]).await;
+    assert_eq!(error_code(result), poker_error(PokerError::PlayerNotFound));
+    harness.send(&[vote_run_twice(&table, &alice.pubkey(), true), vote_run_twice(&table, &bob.pubkey(), true)], &[&alice, &bob]).await.unwrap();
+
+    let state = harness.table(&table).await;
+    let (first, second) = if state.current_player() == Some(0) { (&alice, &bob) } else { (&bob, &alice) };
+    harness.send(&[action(&table, &first.pubkey(), ActionType::AllIn, None)], &[first]).await.unwrap();
+    harness.send(&[action(&table, &second.pubkey(), ActionType::Call, None)], &[second]).await.unwrap();
+
+    let state = harness.table(&table).await;
+    assert_eq!(state.game_state(), GameState::Showdown);
+    let second_board = state.second_board().unwrap();
+    assert!(second_board.iter().all(|c| c.is_some() && !state.community_cards().contains(c)));
+
+    harness.send(&[showdown(&table)], &[]).await.unwrap();
+    harness.send(&[distribute_pot(&table, &[alice.pubkey(), bob.pubkey()])], &[]).await.unwrap();
+    let state = harness.table(&table).await;
+    assert_eq!(chips(&state), 2 * BUY_IN);
+    assert_eq!(state.second_board(), None);
+    assert_eq!(state.run_twice, [0; 9]);
+}
+
+#[tokio::test]
+async fn rabbit_hunt_needs_a_finished_hand_with_undealt_cards() {
+    let mut harness = Harness::start().await;
+    let (creator, alice, bob) = (harness.wallet().await, harness.wallet().await, harness.wallet().await);
+    let table = harness.seated_table(&creator, &[&alice, &bob]).await;
+    harness.send(&[set_muck(&table, &alice.pubkey(), true)], &[&alice]).await.unwrap();
+    assert!(harness.table(&table).await.mucks(0));
+    harness.send(&[start_game(&table, &creator.pubkey())], &[&creator]).await.unwrap();
+    check_down(&mut harness, &table, &[&alice, &bob]).await;
+
+    let result = harness.send(&[rabbit_hunt(&table)], &[]).await;
+    assert_eq!(error_code(result), poker_error(PokerError::InvalidGameState));
+    harness.send(&[showdown(&table)], &[]).await.unwrap();
+    // Checked down to the river, so every card was dealt
+    let result = harness.send(&[rabbit_hunt(&table)], &[]).await;
+    assert_eq!(error_code(result), poker_error(PokerError::InvalidAction));
+
+    harness.send(&[distribute_pot(&table, &[alice.pubkey(), bob.pubkey()])], &[]).await.unwrap();
+    assert!(harness.table(&table).await.mucks(0));
+
+    // Folded preflop: nothing was dealt, so the whole board can be hunted
+    harness.send(&[start_game(&table, &creator.pubkey())], &[&creator]).await.unwrap();
+    let state = harness.table(&table).await;
+    let folder = if state.current_player() == Some(0) { &alice } else { &bob };
+    harness.send(&[action(&table, &folder.pubkey(), ActionType::Fold, None)], &[folder]).await.unwrap();
+    let state = harness.table(&table).await;
+    assert_eq!(state.game_state(), GameState::Finished);
+    assert_eq!(state.community_cards(), [None; 5]);
+    harness.send(&[rabbit_hunt(&table)], &[]).await.unwrap();
+    harness.send(&[distribute_pot(&table, &[alice.pubkey(), bob.pubkey()])], &[]).await.unwrap();
+}
+
+#[tokio::test]
+async fn show_or_muck_applies_to_the_current_hand_only() {
+    let mut harness = Harness::start().await;
+    let (creator, alice, bob) = (harness.wallet().await, harness.wallet().await, harness.wallet().await);
+    let table = harness.seated_table(&creator, &[&alice, &bob]).await;
+    let result = harness.send(&[show_or_muck(&table, &alice.pubkey(), false)], &[&alice]).await;
+    assert_eq!(error_code(result), poker_error(PokerError::InvalidGameState));
+    harness.send(&[start_game(&table, &creator.pubkey())], &[&creator]).await.unwrap();
+
+    // A raise makes its maker the one to show first if nobody re-raises
+    let state = harness.table(&table).await;
+    let seat = state.current_player().unwrap();
+    let first = if seat == 0 { &alice } else { &bob };
+    harness.send(&[action(&table, &first.pubkey(), ActionType::Raise, Some(BIG_BLIND))], &[first]).await.unwrap();
+    assert_eq!(harness.table(&table).await.last_aggressor(), Some(seat));
+
+    harness.send(&[show_or_muck(&table, &alice.pubkey(), false)], &[&alice]).await.unwrap();
+    assert_eq!(harness.table(&table).await.show_decision(0), Some(false));
+    check_down(&mut harness, &table, &[&alice, &bob]).await;
+    // Checking down every street after the flop leaves no aggressor
+    assert_eq!(harness.table(&table).await.last_aggressor(), None);
+
+    harness.send(&[showdown(&table), distribute_pot(&table, &[alice.pubkey(), bob.pubkey()])], &[]).await.unwrap();
+    assert_eq!(harness.table(&table).await.show_decision(0), None);
+}
+
+#[tokio::test]
+async fn uncalled_raise_goes_back_to_the_raiser() {
+    let mut harness = Harness::start().await;
+    let (creator, alice, bob) = (harness.wallet().await, harness.wallet().await, harness.wallet().await);
+    let table = harness.seated_table(&creator, &[&alice, &bob]).await;
+    harness.send(&[start_game(&table, &creator.pubkey())], &[&creator]).await.unwrap();
+
+    let state = harness.table(&table).await;
+    let seat = state.current_player().unwrap();
+    let (raiser, folder) = if seat == 0 { (&alice, &bob) } else { (&bob, &alice) };
+    harness
+        .send(&[action(&table, &raiser.pubkey(), ActionType::Raise, Some(5 * BIG_BLIND))], &[raiser])
+        .await
+        .unwrap();
+    harness.send(&[action(&table, &folder.pubkey(), ActionType::Fold, None)], &[folder]).await.unwrap();
+
+    // Heads-up the raiser is the small blind, so only the folder's big blind
+    // was matched and the rest of the raise is back in their stack
+    let state = harness.table(&table).await;
+    let player = state.get_player(seat).unwrap();
+    assert_eq!(player.bet, BIG_BLIND);
+    assert_eq!(player.stack, BUY_IN - BIG_BLIND);
+    assert_eq!(state.pot, 2 * BIG_BLIND);
+
+    harness.send(&[distribute_pot(&table, &[alice.pubkey(), bob.pubkey()])], &[]).await.unwrap();
+    let state = harness.table(&table).await;
+    assert_eq!(state.get_player(seat).unwrap().stack + state.get_player(1 - seat).unwrap().stack, 2 * BUY_IN);
+}
+
+#[tokio::test]
+async fn jackpot_tables_pay_a_drop_from_pots_that_see_a_flop() {
+    let mut harness = Harness::start().await;
+    let (creator, alice, bob) = (harness.wallet().await, harness.wallet().await, harness.wallet().await);
+    let table = harness.seated_table(&creator, &[&alice, &bob]).await;
+    let admin = harness.admin.insecure_clone();
+    let fund = solana_sdk::system_instruction::transfer(&harness.payer(), &admin.pubkey(), WALLET_LAMPORTS);
+    harness.send(&[fund], &[]).await.unwrap();
+
+    // Only the admin configures the jackpot, and tables need one to join
+    let result = harness.send(&[set_table_jackpot(&table, &creator.pubkey(), true)], &[&creator]).await;
+    assert_eq!(error_code(result), ErrorCode::AccountNotInitialized as u32);
+    let result = harness.send(&[set_jackpot(&alice.pubkey(), 500, 5_000, 2_500, 2_500)], &[&alice]).await;
+    assert_eq!(error_code(result), poker_error(PokerError::Unauthorized));
+    let result = harness.send(&[set_jackpot(&admin.pubkey(), 500, 5_000, 2_500, 2_000)], &[&admin]).await;
+    assert_eq!(error_code(result), poker_error(PokerError::InvalidJackpotConfig));
+    harness.send(&[set_jackpot(&admin.pubkey(), 500, 5_000, 2_500, 2_500)], &[&admin]).await.unwrap();
+    harness.send(&[set_table_jackpot(&table, &creator.pubkey(), true)], &[&creator]).await.unwrap();
+
+    harness.send(&[start_game(&table, &creator.pubkey())], &[&creator]).await.unwrap();
+    let result = harness.send(&[set_table_jackpot(&table, &creator.pubkey(), false)], &[&creator]).await;
+    assert_eq!(error_code(result), poker_error(PokerError::GameInProgress));
+    check_down(&mut harness, &table, &[&alice, &bob]).await;
+    harness.send(&[showdown(&table)], &[]).await.unwrap();
+
+    let players = [alice.pubkey(), bob.pubkey()];
+    let result = harness.send(&[distribute_pot(&table, &players)], &[]).await;
+    assert_eq!(error_code(result), poker_error(PokerError::JackpotRequired));
+    harness.send(&[distribute_jackpot_pot(&table, &players)], &[]).await.unwrap();
+
+    // 5% of the 20 chip pot
+    let jackpot: Jackpot = harness.account(&jackpot_pda()).await;
+    assert_eq!(jackpot.balance, 1);
+    let state = harness.table(&table).await;
+    let stacks: u64 = (0..2).map(|seat| state.get_player(seat).unwrap().stack).sum();
+    assert_eq!(stacks, 2 * BUY_IN - 1);
+}
+
+#[tokio::test]
+async fn jackpot_is_shared_by_every_table() {
+    let mut harness = Harness::start().await;
+    let (creator, alice, bob, carol, dave) =
+        (harness.wallet().await, harness.wallet().await, harness.wallet().await, harness.wallet().await, harness.wallet().await);
+    let admin = harness.admin.insecure_clone();
+    let fund = solana_sdk::system_instruction::transfer(&harness.payer(), &admin.pubkey(), WALLET_LAMPORTS);
+    harness.send(&[fund], &[]).await.unwrap();
+    harness.send(&[set_jackpot(&admin.pubkey(), 500, 5_000, 2_500, 2_500)], &[&admin]).await.unwrap();
+
+    let first = harness.seated_table(&creator, &[&alice, &bob]).await;
+    harness.send(&[create_table(&creator.pubkey(), 2, 9, TableAccess::Public, None)], &[&creator]).await.unwrap();
+    let second = table_pda(&creator.pubkey(), 2);
+    for (seat, player) in [&carol, &dave].iter().enumerate() {
+        harness.send(&[join_table(&second, &player.pubkey(), seat as u8)], &[player]).await.unwrap();
+    }
+
+    for table in [&first, &second] {
+        harness.send(&[set_table_jackpot(table, &creator.pubkey(), true)], &[&creator]).await.unwrap();
+    }
+
+    // An all-in at the first table pays 5% of 2000 chips in
+    harness.send(&[start_game(&first, &creator.pubkey())], &[&creator]).await.unwrap();
+    let (shover, caller) = if harness.table(&first).await.current_player() == Some(0) { (&alice, &bob) } else { (&bob, &alice) };
+    harness.send(&[action(&first, &shover.pubkey(), ActionType::AllIn, None)], &[shover]).await.unwrap();
+    harness.send(&[action(&first, &caller.pubkey(), ActionType::Call, None)], &[caller]).await.unwrap();
+    harness.send(&[showdown(&first)], &[]).await.unwrap();
+    harness.send(&[distribute_jackpot_pot(&first, &[alice.pubkey(), bob.pubkey()])], &[]).await.unwrap();
+    let jackpot: Jackpot = harness.account(&jackpot_pda()).await;
+    assert_eq!(jackpot.balance, 100);
+
+    // Quad eights losing to a straight flush at the second table draws on it
+    harness.send(&[start_game(&second, &creator.pubkey())], &[&creator]).await.unwrap();
+    check_down(&mut harness, &second, &[&carol, &dave]).await;
+    let mut state = harness.table(&second).await;
+    let clubs = |rank| Some(Card::new(Suit::Clubs, rank));
+    state.set_community_cards([clubs(Rank::Eight), Some(Card::new(Suit::Diamonds, Rank::Eight)), clubs(Rank::Nine), clubs(Rank::Ten), clubs(Rank::Jack)]);
+    let hands = [
+        [Some(Card::new(Suit::Hearts, Rank::Eight)), Some(Card::new(Suit::Spades, Rank::Eight))],
+        [clubs(Rank::Queen), clubs(Rank::King)],
+    ];
+    for (seat, hole_cards) in hands.into_iter().enumerate() {
+        let player = Player { hole_cards, ..state.get_player(seat as u8).unwrap() };
+        state.set_player(seat as u8, Some(&player));
+    }
+    let mut data = Table::DISCRIMINATOR.to_vec();
+    data.extend_from_slice(bytemuck::bytes_of(&state));
+    store_table(&mut harness, &second, data);
+    harness.send(&[showdown(&second)], &[]).await.unwrap();
+    harness.send(&[distribute_jackpot_pot(&second, &[carol.pubkey(), dave.pubkey()])], &[]).await.unwrap();
+
+    // Half and a quarter of the 101 chip balance, after this pot's 1 chip drop
+    let jackpot: Jackpot = harness.account(&jackpot_pda()).await;
+    assert_eq!(jackpot.balance, 26);
+    let state = harness.table(&second).await;
+    assert_eq!(state.get_player(0).unwrap().stack, BUY_IN - BIG_BLIND + 50);
+    assert_eq!(state.get_player(1).unwrap().stack, BUY_IN + BIG_BLIND - 1 + 25);
+}
+
+#[tokio::test]
+async fn uncontested_winner_leaving_before_settlement_is_paid_first() {
+    let mut harness = Harness::start().await;
+    let (creator, alice, bob) = (harness.wallet().await, harness.wallet().await, harness.wallet().await);
+    let table = harness.seated_table(&creator, &[&alice, &bob]).await;
+    harness.send(&[start_game(&table, &creator.pubkey())], &[&creator]).await.unwrap();
+
+    let state = harness.table(&table).await;
+    let (folder, winner) = if state.current_player() == Some(0) { (&alice, &bob) } else { (&bob, &alice) };
+    harness.send(&[action(&table, &folder.pubkey(), ActionType::Fold, None)], &[folder]).await.unwrap();
+    assert_eq!(harness.table(&table).await.game_state(), GameState::Finished);
+
+    // The hand is over but not paid out, so the winner stays seated until it is
+    let seat = state.find_seat(&winner.pubkey()).unwrap();
+    harness.send(&[leave_table(&table, &winner.pubkey())], &[winner]).await.unwrap();
+    let state = harness.table(&table).await;
+    assert!(state.is_leaving(seat));
+    assert_eq!(state.player_count, 2);
+
+    harness.send(&[distribute_pot(&table, &[alice.pubkey(), bob.pubkey()])], &[]).await.unwrap();
+    let state = harness.table(&table).await;
+    assert_eq!(state.game_state(), GameState::Waiting);
+    assert_eq!(state.find_seat(&winner.pubkey()), None);
+    assert_eq!(state.player_count, 1);
+    assert_eq!(state.pot, 0);
+}
+
+#[tokio::test]
+async fn seats_freed_after_a_hand_go_to_the_waitlist() {
+    let mut harness = Harness::start().await;
+    let (creator, alice, bob, carol, dave) =
+        (harness.wallet().await, harness.wallet().await, harness.wallet().await, harness.wallet().await, harness.wallet().await);
+    let table = full_table(&mut harness, &creator, [&alice, &bob]).await;
+    harness.send(&[join_waitlist(&table, &carol.pubkey(), false)], &[&carol]).await.unwrap();
+    harness.send(&[join_waitlist(&table, &dave.pubkey(), true)], &[&dave]).await.unwrap();
+    harness.send(&[start_game(&table, &creator.pubkey())], &[&creator]).await.unwrap();
+
+    // Both leave mid-hand, so their seats only free up at settlement
+    harness.send(&[leave_table(&table, &alice.pubkey())], &[&alice]).await.unwrap();
+    harness.send(&[leave_table(&table, &bob.pubkey())], &[&bob]).await.unwrap();
+    assert_eq!(harness.table(&table).await.player_count, 2);
+
+    let players = [alice.pubkey(), bob.pubkey()];
+    let result = harness.send(&[distribute_pot(&table, &players)], &[]).await;
+    assert_eq!(error_code(result), poker_error(PokerError::WaitlistMismatch));
+    let result = harness.send(&[distribute_pot_to_waitlist(&table, &players, &[])], &[]).await;
+    assert_eq!(error_code(result), poker_error(PokerError::WaitlistMismatch));
+
+    // Carol gets a hold on the first seat and dave is seated with the deposit back
+    let dave_before = harness.lamports(&dave.pubkey()).await;
+    harness.send(&[distribute_pot_to_waitlist(&table, &players, &[dave.pubkey()])], &[]).await.unwrap();
+    let state = harness.table(&table).await;
+    let now = harness.clock().await.unix_timestamp;
+    assert_eq!(state.reservation(0, now), Some(carol.pubkey()));
+    assert_eq!(state.find_seat(&dave.pubkey()), Some(1));
+    assert_eq!((state.player_count, state.waitlist_len), (1, 0));
+    assert_eq!(harness.lamports(&dave.pubkey()).await, dave_before + BUY_IN);
+    harness.send(&[join_from_waitlist(&table, &carol.pubkey(), 0)], &[&carol]).await.unwrap();
+}
@@ -0,0 +1,111 @@
+use anchor_lang::prelude::Pubkey;
+use multiplayer_poker::error::PokerError;
+use multiplayer_poker::state::*;
+use proptest::prelude::*;
+
+fn empty_table(small_blind: u64, big_blind: u64) -> Table {
+    Table {
+        creator: Pubkey::new_unique(),
+        table_id: 1,
+        small_blind,
+        big_blind,
+        max_players: 9,
+        buy_in: big_blind * 10,
+        min_raise: big_blind,
+        action_timeout: 60,
+        ..Table::default()
+    }
+}
+
+fn chips(table: &Table) -> u64 {
+    table.players().iter().flatten().map(|p| p.stack).sum::<u64>() + table.pot
+}
+
+fn action_type() -> impl Strategy<Value = ActionType> {
+    prop_oneof![
+        Just(ActionType::Fold),
+        Just(ActionType::Check),
+        Just(ActionType::Call),
+        Just(ActionType::Raise),
+        Just(ActionType::AllIn),
+    ]
+}
+
+/// Same path as `action::handler`: load, act, store
+fn act(table: &mut Table, player: &Pubkey, action_type: ActionType, amount: Option<u64>) -> Result<(), PokerError> {
+    let seat = table.find_seat(player).ok_or(PokerError::PlayerNotFound)?;
+    let mut game = table.to_engine();
+    game.act(seat, action_type.into(), amount)?;
+    table.apply_engine(&game);
+    Ok(())
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(256))]
+
+    #[test]
+    fn action_sequences_on_table_account(
+        stacks in prop::collection::vec(prop::option::weighted(0.7, 1u64..2_000), 9),
+        deck_seed in any::<[u8; 32]>(),
+        steps in prop::collection::vec((0usize..10, action_type(), prop::option::of(0u64..1_000)), 0..150),
+    ) {
+        let mut table = empty_table(5, 10);
+        let keys: Vec<Pubkey> = (0..10).map(|_| Pubkey::new_unique()).collect();
+        let mut game = table.to_engine();
+        for (seat, stack) in stacks.iter().enumerate() {
+            if let Some(stack) = stack {
+                game.seat_player(seat as u8, keys[seat].to_bytes(), *stack).unwrap();
+            }
+        }
+        table.apply_engine(&game);
+        prop_assume!(game.start_hand(deck_seed).is_ok());
+        table.apply_engine(&game);
+        let total = chips(&table);
+
+        for (key, action_type, amount) in steps {
+            let before = table.to_engine();
+            if act(&mut table, &keys[key], action_type, amount).is_err() {
+                prop_assert_eq!(table.to_engine(), before);
+            }
+            // The account stores exactly what the engine computed
+            let game = table.to_engine();
+            let mut reloaded = table;
+            reloaded.apply_engine(&game);
+            prop_assert_eq!(reloaded.to_engine(), game.clone());
+            prop_assert_eq!(game.check_invariants(), Ok(()));
+            prop_assert_eq!(chips(&table), total);
+            if let Some(current) = table.current_player() {
+                let player = table.get_player(current).unwrap();
+                prop_assert!(!player.has_folded && !player.is_all_in);
+            }
+        }
+
+        // Check or call down, then run the showdown and distribution paths
+        while !matches!(table.game_state(), GameState::Showdown | GameState::Finished) {
+            let seat = table.current_player().unwrap();
+            let player = table.get_player(seat).unwrap();
+            let current_bet = table.to_engine().current_bet();
+            let action = if player.bet < current_bet { ActionType::Call } else { ActionType::Check };
+            act(&mut table, &player.pubkey, action, None).unwrap();
+        }
+
+        // Hands everyone else folded end without a showdown
+        if table.game_state() == GameState::Showdown {
+            let mut game = table.to_engine();
+            let result = game.showdown().unwrap();
+            table.apply_engine(&game);
+            for (seat, hand) in result.hands.iter().enumerate() {
+                if hand.is_some() {
+                    let player = table.get_player(seat as u8).unwrap();
+                    prop_assert!(player.hole_cards.iter().all(Option::is_some));
+                }
+            }
+        }
+
+        let mut game = table.to_engine();
+        game.settle().unwrap();
+        table.apply_engine(&game);
+        prop_assert_eq!(table.game_state(), GameState::Waiting);
+        prop_assert_eq!(chips(&table), total);
+    }
+}
@@ -22,6 +22,9 @@ describe("multiplayer-poker", () => {
   let tablePDA: PublicKey;
   let tableBump: number;
 
+  const profilePDA = (player: PublicKey) =>
+    PublicKey.findProgramAddressSync([Buffer.from("profile"), player.toBuffer()], program.programId)[0];
+
   before(async () => {
     // Airdrop SOL to test players
     const airdrop1 = await provider.connection.requestAirdrop(player1.publicKey, 2 * anchor.web3.LAMPORTS_PER_SOL);
@@ -42,7 +45,7 @@ describe("multiplayer-poker", () => {
 
   it("Creates a table", async () => {
     const tx = await program.methods
-      .createTable(tableId, smallBlind, bigBlind, maxPlayers, buyIn)
+      .createTable(tableId, smallBlind, bigBlind, maxPlayers, buyIn, { public: {} }, null)
       .accounts({
         table: tablePDA,
         creator: creator.publicKey,
@@ -61,10 +64,15 @@ describe("multiplayer-poker", () => {
   it("Player joins table", async () => {
     const seat = 0;
     const tx = await program.methods
-      .joinTable(seat)
+      .joinTable(seat, null)
       .accounts({
         table: tablePDA,
         player: player1.publicKey,
+        profile: profilePDA(player1.publicKey),
+        allowlist: null,
+        gateTokenAccount: null,
+        gateMetadata: null,
+        waitlist: null,
         systemProgram: SystemProgram.programId,
       })
       .signers([player1])
@@ -72,16 +80,25 @@ describe("multiplayer-poker", () => {
 
     const table = await program.account.table.fetch(tablePDA);
     expect(table.playerCount).to.equal(1);
-    expect(table.players[seat]).to.not.be.null;
+    expect(table.players[seat].occupied).to.equal(1);
+
+    const profile = await program.account.playerProfile.fetch(profilePDA(player1.publicKey));
+    expect(profile.player.toBase58()).to.equal(player1.publicKey.toBase58());
+    expect(profile.handsPlayed.toNumber()).to.equal(0);
   });
 
   it("Second player joins table", async () => {
     const seat = 1;
     const tx = await program.methods
-      .joinTable(seat)
+      .joinTable(seat, null)
       .accounts({
         table: tablePDA,
         player: player2.publicKey,
+        profile: profilePDA(player2.publicKey),
+        allowlist: null,
+        gateTokenAccount: null,
+        gateMetadata: null,
+        waitlist: null,
         systemProgram: SystemProgram.programId,
       })
       .signers([player2])
@@ -97,6 +114,9 @@ describe("multiplayer-poker", () => {
       .accounts({
         table: tablePDA,
         authority: creator.publicKey,
+        relayer: null,
+        sponsor: null,
+        instructions: null,
       })
       .rpc();
 
@@ -111,13 +131,18 @@ describe("multiplayer-poker", () => {
       .action(actionType, null)
       .accounts({
         table: tablePDA,
+        signer: player1.publicKey,
         player: player1.publicKey,
+        session: null,
+        relayer: null,
+        sponsor: null,
+        instructions: null,
       })
       .signers([player1])
       .rpc();
 
     const table = await program.account.table.fetch(tablePDA);
     const player = table.players[0];
-    expect(player.hasActed).to.be.true;
+    expect(player.hasActed).to.equal(1);
   });
 });